@@ -1,3 +1,4 @@
+use bible::binary_format;
 use bible::csv_import::bible_import;
 use criterion::{criterion_group, criterion_main, Criterion};
 use std::error::Error;
@@ -19,6 +20,13 @@ fn bible_import_benchmark(c: &mut Criterion) {
             c.bench_function(&format!("import {}", file_name), |b| {
                 b.iter(|| bible_import(black_box(path.to_str().unwrap())))
             });
+
+            // The same translation loaded from the precompiled format that BIBLES prefers.
+            let bible = bible_import(path.to_str().unwrap()).expect("Failed to import bible");
+            let bytes = binary_format::to_bytes(&bible);
+            c.bench_function(&format!("import {} precompiled", file_name), |b| {
+                b.iter(|| binary_format::from_bytes(black_box(&bytes)))
+            });
         }
     }
 }
@@ -48,9 +56,43 @@ fn bible_get_scripture_benchmark(c: &mut Criterion) {
     }
 }
 
+fn bible_navigation_benchmark(c: &mut Criterion) {
+    let bibles_directory = env::current_dir()
+        .expect("Failed to get current directory")
+        .join("bibles");
+    let files = fs::read_dir(&bibles_directory).expect("Failed to read directory");
+
+    for file in files {
+        let file = file.expect("Failed to read file");
+        let path = file.path();
+        if path.extension().and_then(std::ffi::OsStr::to_str) == Some("csv") {
+            let file_name = path.file_name().unwrap().to_str().unwrap();
+            let bible = bible_import(path.to_str().unwrap()).expect("Failed to import bible");
+
+            // The last verse of the Bible is the worst case for a scan of the verses.
+            c.bench_function(&format!("get_previous_scripture {}", file_name), |b| {
+                b.iter(|| bible.get_previous_scripture(black_box("Revelation 22:21"), 1))
+            });
+            c.bench_function(&format!("get_next_scripture {}", file_name), |b| {
+                b.iter(|| bible.get_next_scripture(black_box("Revelation 22:20"), 1))
+            });
+            c.bench_function(&format!("get_scripture Psalm 119 {}", file_name), |b| {
+                b.iter(|| bible.get_scripture(black_box("Psalm 119")))
+            });
+            c.bench_function(&format!("get_scripture Revelation 22:21 {}", file_name), |b| {
+                b.iter(|| bible.get_scripture(black_box("Revelation 22:21")))
+            });
+            c.bench_function(&format!("random_scripture {}", file_name), |b| {
+                b.iter(|| bible.random_scripture().len())
+            });
+        }
+    }
+}
+
 criterion_group!(
     benches,
     bible_import_benchmark,
-    bible_get_scripture_benchmark
+    bible_get_scripture_benchmark,
+    bible_navigation_benchmark
 );
 criterion_main!(benches);
@@ -53,15 +53,29 @@ fn main() {
             e
         );
     } else {
-        println!("cargo:warning=CSV files will be copied to: {:?}", dest_dir);
+        println!("cargo:warning=Bible files will be copied to: {:?}", dest_dir);
     }
 
-    // Copy each CSV file from the source to the target directory
+    // Copy each precompiled, CSV, OSIS, Zefania and USFM file from the source to the target
+    // directory, along with USFM translations kept as a directory of book files
+    copy_bibles(src_dir, &dest_dir);
+}
+
+fn copy_bibles(src_dir: &Path, dest_dir: &Path) {
     for entry in fs::read_dir(src_dir).unwrap() {
         let entry = entry.unwrap();
         let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("csv") {
-            let dest_path = dest_dir.join(entry.file_name());
+        let dest_path = dest_dir.join(entry.file_name());
+        if path.is_dir() {
+            if let Err(e) = fs::create_dir_all(&dest_path) {
+                println!("cargo:warning=Failed to create directory: {:?}", e);
+            } else {
+                copy_bibles(&path, &dest_path);
+            }
+        } else if matches!(
+            path.extension().and_then(|s| s.to_str()),
+            Some("cvb" | "csv" | "osis" | "xml" | "usfm" | "sfm")
+        ) {
             if let Err(e) = fs::copy(path, dest_path) {
                 println!("cargo:warning=Failed to copy file: {:?}", e);
             }
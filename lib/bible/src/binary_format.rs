@@ -0,0 +1,305 @@
+use crate::scripture::bible::{Bible, Verse};
+use crate::scripture::markup::{Markup, Span};
+use crate::scripture::search::SearchIndex;
+use crate::scripture::strongs::{StrongsNumber, TaggedWord};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+// A precompiled Bible, so startup does not parse text or rebuild the search index.
+//
+// Header: the MAGIC bytes, the format VERSION, a CRC32 of the payload and the payload length.
+// Payload: the verse count, then each verse as book, chapter and verse bytes, its reference and
+// abbreviation as given by the source, its UTF-8 text, its Strong's tagged words, its markup
+// spans and its headings, then the search index word count,
+// then each word and its postings.  A tagged word is its text and its numbers, each an H or G
+// byte and a u16.  A span is a J, S or F byte for words of Jesus, supplied words or a footnote,
+// its start and end, and for a footnote its note.  Headings are text.  Integers are
+// little-endian, and text and lists are prefixed with their length.
+const MAGIC: &[u8; 8] = b"CVBIBLE\0";
+const HEADER_LENGTH: usize = 24;
+
+/// Files written with another version must be converted again.
+pub const VERSION: u32 = 5;
+
+/// The extension of precompiled Bible files.
+pub const EXTENSION: &str = "cvb";
+
+pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
+    from_bytes(&fs::read(bible_import_path)?)
+}
+
+pub fn bible_export(bible: &Bible, bible_export_path: &str) -> Result<(), Box<dyn Error>> {
+    Ok(fs::write(bible_export_path, to_bytes(bible))?)
+}
+
+pub fn to_bytes(bible: &Bible) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend((bible.verses().len() as u32).to_le_bytes());
+    for verse in bible.verses() {
+        payload.extend([verse.book, verse.chapter, verse.verse]);
+        for text in [&verse.reference, &verse.abbreviation, &verse.scripture] {
+            payload.extend((text.len() as u32).to_le_bytes());
+            payload.extend(text.as_bytes());
+        }
+        payload.extend((verse.words.len() as u32).to_le_bytes());
+        for word in &verse.words {
+            payload.extend((word.text.len() as u32).to_le_bytes());
+            payload.extend(word.text.as_bytes());
+            payload.extend((word.strongs.len() as u32).to_le_bytes());
+            for number in &word.strongs {
+                let (language, number) = match *number {
+                    StrongsNumber::Hebrew(number) => (b'H', number),
+                    StrongsNumber::Greek(number) => (b'G', number),
+                };
+                payload.push(language);
+                payload.extend(number.to_le_bytes());
+            }
+        }
+        payload.extend((verse.spans.len() as u32).to_le_bytes());
+        for span in &verse.spans {
+            payload.push(match span.markup {
+                Markup::WordsOfJesus => b'J',
+                Markup::Supplied => b'S',
+                Markup::Footnote(_) => b'F',
+            });
+            payload.extend((span.start as u32).to_le_bytes());
+            payload.extend((span.end as u32).to_le_bytes());
+            if let Markup::Footnote(note) = &span.markup {
+                payload.extend((note.len() as u32).to_le_bytes());
+                payload.extend(note.as_bytes());
+            }
+        }
+        payload.extend((verse.headings.len() as u32).to_le_bytes());
+        for heading in &verse.headings {
+            payload.extend((heading.len() as u32).to_le_bytes());
+            payload.extend(heading.as_bytes());
+        }
+    }
+
+    // Sorted so converting the same Bible twice writes the same file.
+    let mut words: Vec<_> = bible.search_index().words().iter().collect();
+    words.sort_unstable_by_key(|(word, _)| *word);
+    payload.extend((words.len() as u32).to_le_bytes());
+    for (word, postings) in words {
+        payload.extend((word.len() as u32).to_le_bytes());
+        payload.extend(word.as_bytes());
+        payload.extend((postings.len() as u32).to_le_bytes());
+        for posting in postings {
+            payload.extend(posting.to_le_bytes());
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(HEADER_LENGTH + payload.len());
+    bytes.extend(MAGIC);
+    bytes.extend(VERSION.to_le_bytes());
+    bytes.extend(crc32fast::hash(&payload).to_le_bytes());
+    bytes.extend((payload.len() as u64).to_le_bytes());
+    bytes.extend(payload);
+    bytes
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<Bible, Box<dyn Error>> {
+    let mut header = Cursor { bytes, position: 0 };
+    if header.take(MAGIC.len())? != MAGIC {
+        return Err("Not a precompiled ChapterVerse Bible".into());
+    }
+    let version = header.u32()?;
+    if version != VERSION {
+        return Err(format!(
+            "Precompiled Bible is version {}, but version {} is required, convert it again",
+            version, VERSION
+        )
+        .into());
+    }
+    let checksum = header.u32()?;
+    let length = header.u64()? as usize;
+    let payload = header.take(length)?;
+    if crc32fast::hash(payload) != checksum {
+        return Err("Precompiled Bible failed its checksum, convert it again".into());
+    }
+
+    let mut payload = Cursor {
+        bytes: payload,
+        position: 0,
+    };
+    let verse_count = payload.u32()? as usize;
+    let mut verses = Vec::with_capacity(verse_count);
+    for _ in 0..verse_count {
+        let numbers = payload.take(3)?;
+        let (book, chapter, verse) = (numbers[0], numbers[1], numbers[2]);
+        let (reference, abbreviation) = (payload.string()?, payload.string()?);
+        let mut verse = Verse {
+            reference,
+            abbreviation,
+            ..Verse::new(book, chapter, verse, payload.string()?)
+        };
+        let word_count = payload.u32()? as usize;
+        for _ in 0..word_count {
+            let text = payload.string()?;
+            let number_count = payload.u32()? as usize;
+            let strongs = (0..number_count)
+                .map(|_| payload.strongs_number())
+                .collect::<Result<Vec<_>, _>>()?;
+            verse.words.push(TaggedWord { text, strongs });
+        }
+        let span_count = payload.u32()? as usize;
+        for _ in 0..span_count {
+            verse.spans.push(payload.span(&verse.scripture)?);
+        }
+        let heading_count = payload.u32()? as usize;
+        for _ in 0..heading_count {
+            verse.headings.push(payload.string()?);
+        }
+        verses.push(verse);
+    }
+
+    let word_count = payload.u32()? as usize;
+    let mut words = HashMap::with_capacity(word_count);
+    for _ in 0..word_count {
+        let word = payload.string()?;
+        let posting_count = payload.u32()? as usize;
+        let postings = (0..posting_count)
+            .map(|_| payload.u32())
+            .collect::<Result<Vec<_>, _>>()?;
+        words.insert(word, postings);
+    }
+
+    let search_index = SearchIndex::from_words(words, verses.len());
+    Ok(Bible::from_parts(verses, search_index))
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        let bytes = self
+            .position
+            .checked_add(length)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or("Precompiled Bible is truncated")?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, Box<dyn Error>> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn string(&mut self) -> Result<String, Box<dyn Error>> {
+        let length = self.u32()? as usize;
+        Ok(String::from_utf8(self.take(length)?.to_vec())?)
+    }
+
+    fn strongs_number(&mut self) -> Result<StrongsNumber, Box<dyn Error>> {
+        let language = self.take(1)?[0];
+        let number = self.u16()?;
+        match language {
+            b'H' => Ok(StrongsNumber::Hebrew(number)),
+            b'G' => Ok(StrongsNumber::Greek(number)),
+            _ => Err("Precompiled Bible has an unknown Strong's number".into()),
+        }
+    }
+
+    // A span of a verse's text, checked to be within it so rendering cannot go out of bounds.
+    fn span(&mut self, text: &str) -> Result<Span, Box<dyn Error>> {
+        let kind = self.take(1)?[0];
+        let (start, end) = (self.u32()? as usize, self.u32()? as usize);
+        let markup = match kind {
+            b'J' => Markup::WordsOfJesus,
+            b'S' => Markup::Supplied,
+            b'F' => Markup::Footnote(self.string()?),
+            _ => return Err("Precompiled Bible has an unknown span".into()),
+        };
+        if start > end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            return Err("Precompiled Bible has a span outside its verse".into());
+        }
+        Ok(Span { markup, start, end })
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    fn sample_bible() -> Bible {
+        let mut bible = Bible::new();
+        for (book, chapter, verse, text) in [
+            (43, 3, 16, "‹For God so [loved] the world.{Or, so much}›"),
+            (43, 3, 17, "For God sent not his Son."),
+            (45, 5, 8, "But God commendeth his love."),
+        ] {
+            let mut verse = Verse::new(book, chapter, verse, text.to_string()).with_markup();
+            if verse.verse == 16 {
+                verse.headings.push("God's Love for the World".to_string());
+                verse.words.push(TaggedWord {
+                    text: "loved".to_string(),
+                    strongs: vec![StrongsNumber::Greek(25)],
+                });
+            }
+            bible.insert(verse);
+        }
+        bible
+    }
+
+    fn import_error(bytes: &[u8]) -> String {
+        from_bytes(bytes)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn round_trip() {
+        let bible = sample_bible();
+        let loaded = from_bytes(&to_bytes(&bible)).unwrap();
+        assert_eq!(loaded.verses(), bible.verses());
+        assert_eq!(loaded.lookup("John 3:16-17").unwrap().len(), 2);
+        assert_eq!(loaded.verse_count(43, 3).unwrap(), 17);
+        assert_eq!(
+            loaded.search("\"his son\"", 5)[0].reference,
+            bible.search("\"his son\"", 5)[0].reference
+        );
+        assert_eq!(loaded.search_count("god"), 3);
+    }
+
+    #[test]
+    fn round_trip_keeps_source_references() {
+        let bible = crate::csv_import::bible_from_str(
+            "reference,abbreviation,book,chapter,verse,scripture\n\
+             Psalms 23:1,Psa23:1,19,23,1,The LORD is my shepherd; I shall not want.\n",
+        )
+        .unwrap();
+        let loaded = from_bytes(&to_bytes(&bible)).unwrap();
+        assert_eq!(loaded.verses()[0].reference, "Psalms 23:1");
+        assert_eq!(loaded.verses()[0].abbreviation, "Psa23:1");
+        assert_eq!(loaded.verses(), bible.verses());
+    }
+
+    #[test]
+    fn rejects_damaged_files() {
+        let bytes = to_bytes(&sample_bible());
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(import_error(&corrupted).contains("checksum"));
+
+        let mut old_version = bytes.clone();
+        old_version[8] = 0;
+        assert!(import_error(&old_version).contains("version 0"));
+
+        assert!(import_error(&bytes[..bytes.len() - 1]).contains("truncated"));
+        assert!(import_error(b"reference,abbreviation").contains("Not a precompiled"));
+    }
+}
@@ -1,17 +1,179 @@
+pub mod binary_format;
+pub mod osis_import;
 pub mod scripture;
+pub mod usfm_import;
+pub mod validation;
+pub mod zefania_import;
+
+// Verse text from XML and USFM is spread over several lines and markers, so collapse the
+// whitespace.
+pub(crate) fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
 
 pub mod csv_import {
     use crate::scripture::bible::{Bible, Verse};
     use std::error::Error;
+    use std::fs::File;
+    use std::io::Read;
 
     pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
-        let mut csv_reader = csv::Reader::from_path(bible_import_path)?;
+        read_bible(File::open(bible_import_path)?)
+    }
+
+    pub fn bible_from_str(csv: &str) -> Result<Bible, Box<dyn Error>> {
+        read_bible(csv.as_bytes())
+    }
+
+    // Brackets and braces mean different things in different translations, e.g. textual
+    // variants, so the text is only read as markup when its column is named "markup" rather
+    // than "scripture".  [Supplied] words and ‹words of Jesus› are then shown in each channel's
+    // text style.
+    fn read_bible<R: Read>(reader: R) -> Result<Bible, Box<dyn Error>> {
+        let mut csv_reader = csv::Reader::from_reader(reader);
         let mut bible = Bible::new(); // Instantiate ScriptureIndex
 
+        let headers = csv_reader.headers()?.clone();
+        let markup = headers.iter().any(|header| header == "markup");
+        if markup {
+            let renamed = headers.iter().map(|header| match header {
+                "markup" => "scripture",
+                header => header,
+            });
+            csv_reader.set_headers(renamed.collect());
+        }
+
         for result in csv_reader.deserialize() {
             let record: Verse = result?;
+            let record = if markup { record.with_markup() } else { record };
             bible.insert(record); // Insert each Bible record into the index
         }
         Ok(bible) // Return the populated ScriptureIndex
     }
+
+    #[cfg(test)]
+    mod unittests {
+        use super::*;
+        use crate::scripture::markup::{Markup, TextStyle};
+
+        #[test]
+        fn imports_markup() {
+            let bible = bible_from_str(
+                "reference,abbreviation,book,chapter,verse,markup\n\
+                 John 3:3,Jn3:3,43,3,3,\"Jesus answered and said unto him, ‹Verily, verily, I say unto thee, Except a man be born again,{Or, from above} he cannot see the kingdom of God.›\"\n\
+                 John 3:4,Jn3:4,43,3,4,\"Nicodemus saith unto him, How can a man be born when he is old? can he enter the second time into his mother's womb, and be born?\"\n\
+                 John 3:5,Jn3:5,43,3,5,\"Jesus answered, ‹Verily, verily, I say unto thee, Except a man be born of water and [of] the Spirit›\"\n",
+            )
+            .unwrap();
+
+            let verses = bible.lookup("John 3:3-5").unwrap();
+            assert_eq!(verses[0].spans.len(), 2);
+            assert_eq!(verses[0].spans[0].markup, Markup::WordsOfJesus);
+            assert_eq!(
+                verses[0].render(TextStyle::Plain),
+                "Jesus answered and said unto him, Verily, verily, I say unto thee, Except a man be born again, he cannot see the kingdom of God."
+            );
+            assert!(verses[1].spans.is_empty());
+            assert_eq!(
+                verses[2].render(TextStyle::Plain),
+                "Jesus answered, Verily, verily, I say unto thee, Except a man be born of water and of the Spirit"
+            );
+            assert_eq!(
+                verses[2].render(TextStyle::Brackets),
+                "Jesus answered, ‹Verily, verily, I say unto thee, Except a man be born of water and [of] the Spirit›"
+            );
+            // The search index has the words without their markup.
+            assert_eq!(bible.search_count("spirit"), 1);
+        }
+
+        #[test]
+        fn keeps_brackets_without_markup() {
+            let bible = bible_from_str(
+                "reference,abbreviation,book,chapter,verse,scripture\n\
+                 Mark 16:9,Mk16:9,41,16,9,\"[Now when Jesus was risen early the first day of the week,]{He appeared first to Mary Magdalene}\"\n",
+            )
+            .unwrap();
+
+            let verse = &bible.lookup("Mark 16:9").unwrap()[0];
+            assert!(verse.spans.is_empty());
+            assert_eq!(
+                verse.render(TextStyle::Plain),
+                "[Now when Jesus was risen early the first day of the week,]{He appeared first to Mary Magdalene}"
+            );
+        }
+    }
+}
+
+pub mod xml_import {
+    use crate::scripture::bible::Bible;
+    use crate::{osis_import, zefania_import};
+    use std::error::Error;
+    use std::fs::File;
+    use std::io::Read;
+
+    /// Imports an OSIS or Zefania XML file, telling them apart by their root element.
+    pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
+        let mut start = Vec::new();
+        File::open(bible_import_path)?
+            .take(4096)
+            .read_to_end(&mut start)?;
+        let start = String::from_utf8_lossy(&start);
+        if start.contains("<osis") {
+            osis_import::bible_import(bible_import_path)
+        } else if start.contains("<XMLBIBLE") {
+            zefania_import::bible_import(bible_import_path)
+        } else {
+            Err(format!("{} is not an OSIS or Zefania XML bible", bible_import_path).into())
+        }
+    }
+}
+
+pub mod import {
+    use crate::scripture::bible::Bible;
+    use crate::{binary_format, csv_import, osis_import, usfm_import, xml_import};
+    use std::error::Error;
+    use std::path::Path;
+
+    // Supported extensions, most preferred first when a translation is in several formats.
+    const EXTENSIONS: [&str; 6] = [
+        binary_format::EXTENSION,
+        "csv",
+        "osis",
+        "xml",
+        "usfm",
+        "sfm",
+    ];
+
+    /// Imports a Bible in any supported format, choosing the importer by file extension.  A
+    /// directory is a USFM translation with one file per book.
+    pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
+        let path = Path::new(bible_import_path);
+        if path.is_dir() {
+            return usfm_import::bible_import(bible_import_path);
+        }
+        match extension(path).as_deref() {
+            Some(binary_format::EXTENSION) => binary_format::bible_import(bible_import_path),
+            Some("csv") => csv_import::bible_import(bible_import_path),
+            Some("osis") => osis_import::bible_import(bible_import_path),
+            Some("xml") => xml_import::bible_import(bible_import_path),
+            Some("usfm" | "sfm") => usfm_import::bible_import(bible_import_path),
+            _ => Err(format!("{} is not a supported Bible format", bible_import_path).into()),
+        }
+    }
+
+    /// The rank of a path among the formats of one translation, lowest first, so a precompiled
+    /// file is loaded instead of the CSV it was made from.  None when it is not a Bible.
+    pub fn preference(path: &Path) -> Option<usize> {
+        if path.is_dir() {
+            return Some(EXTENSIONS.len());
+        }
+        let extension = extension(path)?;
+        EXTENSIONS.iter().position(|known| *known == extension)
+    }
+
+    fn extension(path: &Path) -> Option<String> {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .map(str::to_lowercase)
+    }
 }
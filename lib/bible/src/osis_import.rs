@@ -0,0 +1,402 @@
+use crate::clean_text;
+use crate::scripture::bible::{Bible, Verse};
+use crate::scripture::markup::escape;
+use crate::scripture::reference::osis_book_id;
+use crate::scripture::strongs::{StrongsNumber, TaggedWord};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::error::Error;
+use std::io::BufRead;
+use std::mem;
+
+// Elements whose text is not part of the verse, such as cross references and book titles.
+// Footnotes are kept as markup and section headings as headings, but notes within them are
+// skipped.
+const SKIPPED_ELEMENTS: [&[u8]; 4] = [b"note", b"title", b"rdg", b"catchWord"];
+
+// Title types that are not headings of a section: book and chapter titles, parallel passages
+// and running heads.
+const SKIPPED_TITLES: [&[u8]; 4] = [b"main", b"chapter", b"parallel", b"runningHead"];
+
+// A verse's book, chapter and verse numbers.
+type Location = (u8, u8, u8);
+
+pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
+    read_bible(Reader::from_file(bible_import_path)?)
+}
+
+pub fn bible_from_str(xml: &str) -> Result<Bible, Box<dyn Error>> {
+    read_bible(Reader::from_str(xml))
+}
+
+// Verses are either containers, <verse osisID="John.3.16">...</verse>, or milestones that
+// mark where the verse starts and ends, <verse sID="John.3.16" osisID="John.3.16"/>...<verse
+// eID="John.3.16"/>.  Both are handled by collecting text from the start of a verse to its end.
+// The text is collected as markup, with supplied words, words of Jesus and footnotes marked.
+// Section headings and Psalm titles, <title type="psalm">, belong to the verse after them.
+fn read_bible<R: BufRead>(mut reader: Reader<R>) -> Result<Bible, Box<dyn Error>> {
+    let mut bible = Bible::new();
+    let mut buffer = Vec::new();
+    let mut current: Option<(u8, u8, u8, String)> = None;
+    // Words tagged with Strong's numbers, as in <w lemma="strong:G2316">God</w>.
+    let mut words: Vec<TaggedWord> = Vec::new();
+    let mut word: Option<TaggedWord> = None;
+    // The open and close marks of the elements open, if they are markup, so quotes that go on
+    // over several verses are opened again in each.
+    let mut marks: Vec<Option<(char, char)>> = Vec::new();
+    // The sIDs of milestone quotes of Jesus, as in <q sID="q1" who="Jesus"/>...<q eID="q1"/>.
+    let mut quotes: Vec<String> = Vec::new();
+    let mut note: Option<String> = None;
+    // The headings of the current verse and those waiting for the next.
+    let mut headings: Vec<String> = Vec::new();
+    let mut pending_headings: Vec<String> = Vec::new();
+    let mut title: Option<String> = None;
+    let mut skip_depth: usize = 0;
+
+    loop {
+        match reader.read_event_into(&mut buffer)? {
+            Event::Eof => break,
+            Event::Start(element)
+                if element.local_name().as_ref() == b"note"
+                    && skip_depth == 0
+                    && note.is_none()
+                    && title.is_none()
+                    && !is_cross_reference(&element)? =>
+            {
+                note = Some(String::new());
+            }
+            Event::End(element) if element.local_name().as_ref() == b"note" && skip_depth == 0 => {
+                if let (Some(note), Some((_, _, _, text))) = (note.take(), current.as_mut()) {
+                    text.push_str(&format!("{{{}}}", escape(&note)));
+                }
+            }
+            Event::Start(element)
+                if element.local_name().as_ref() == b"title"
+                    && skip_depth == 0
+                    && note.is_none()
+                    && title.is_none()
+                    && is_heading(&element)? =>
+            {
+                title = Some(String::new());
+            }
+            Event::End(element) if element.local_name().as_ref() == b"title" && skip_depth == 0 => {
+                let heading = clean_text(&title.take().unwrap_or_default());
+                // A title inside a verse before its words, as some Psalms have, is its heading.
+                let before_words = current
+                    .as_ref()
+                    .is_some_and(|(.., text)| !text.chars().any(char::is_alphanumeric));
+                match (heading.is_empty(), before_words) {
+                    (true, _) => (),
+                    (false, true) => headings.push(heading),
+                    (false, false) => pending_headings.push(heading),
+                }
+            }
+            Event::Start(element) if SKIPPED_ELEMENTS.contains(&element.local_name().as_ref()) => {
+                skip_depth += 1;
+            }
+            Event::End(element) if SKIPPED_ELEMENTS.contains(&element.local_name().as_ref()) => {
+                skip_depth = skip_depth.saturating_sub(1);
+            }
+            _ if skip_depth > 0 => (),
+            Event::Text(text) if note.is_some() => {
+                if let Some(note) = note.as_mut() {
+                    note.push_str(&text.unescape()?);
+                }
+            }
+            _ if note.is_some() => (),
+            Event::Text(text) if title.is_some() => {
+                if let Some(title) = title.as_mut() {
+                    title.push_str(&text.unescape()?);
+                }
+            }
+            _ if title.is_some() => (),
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"verse" =>
+            {
+                if element.try_get_attribute("eID")?.is_some() {
+                    finish_verse(&mut bible, current.take(), &mut words, &mut headings);
+                } else if let Some((book, chapter, verse)) = verse_location(&element)? {
+                    finish_verse(&mut bible, current.take(), &mut words, &mut headings);
+                    headings = mem::take(&mut pending_headings);
+                    let mut text = String::new();
+                    text.extend(marks.iter().flatten().map(|(open, _)| open));
+                    text.extend(quotes.iter().map(|_| '‹'));
+                    current = Some((book, chapter, verse, text));
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"verse" => {
+                finish_verse(&mut bible, current.take(), &mut words, &mut headings);
+            }
+            Event::Start(element) if element.local_name().as_ref() == b"w" => {
+                if let Some((book, ..)) = current.as_ref() {
+                    word = tagged_word(&element, *book)?;
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"w" => {
+                if let Some(mut tagged) = word.take() {
+                    tagged.text = clean_text(&tagged.text);
+                    if !tagged.text.is_empty() {
+                        words.push(tagged);
+                    }
+                }
+            }
+            Event::Start(element)
+                if matches!(element.local_name().as_ref(), b"transChange" | b"q") =>
+            {
+                let mark = markup_marks(&element)?;
+                if let (Some((open, _)), Some((_, _, _, text))) = (mark, current.as_mut()) {
+                    text.push(open);
+                }
+                marks.push(mark);
+            }
+            Event::End(element)
+                if matches!(element.local_name().as_ref(), b"transChange" | b"q") =>
+            {
+                if let (Some(Some((_, close))), Some((_, _, _, text))) =
+                    (marks.pop(), current.as_mut())
+                {
+                    text.push(close);
+                }
+            }
+            Event::Empty(element) if element.local_name().as_ref() == b"q" => {
+                if let Some(id) = element.try_get_attribute("eID")? {
+                    let id = id.unescape_value()?;
+                    if let Some(index) = quotes.iter().position(|quote| *quote == id) {
+                        quotes.remove(index);
+                        if let Some((_, _, _, text)) = current.as_mut() {
+                            text.push('›');
+                        }
+                    }
+                } else if let (Some(id), Some(_)) =
+                    (element.try_get_attribute("sID")?, markup_marks(&element)?)
+                {
+                    quotes.push(id.unescape_value()?.into_owned());
+                    if let Some((_, _, _, text)) = current.as_mut() {
+                        text.push('‹');
+                    }
+                }
+            }
+            Event::Empty(element) if element.local_name().as_ref() == b"lb" => {
+                if let Some((_, _, _, text)) = current.as_mut() {
+                    text.push(' ');
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, _, _, verse_text)) = current.as_mut() {
+                    let text = text.unescape()?;
+                    verse_text.push_str(&escape(&text));
+                    if let Some(tagged) = word.as_mut() {
+                        tagged.text.push_str(&text);
+                    }
+                }
+            }
+            _ => (),
+        }
+        buffer.clear();
+    }
+    finish_verse(&mut bible, current, &mut words, &mut headings);
+    Ok(bible)
+}
+
+// The book, chapter and verse of an osisID such as John.3.16.  Combined verses such as
+// "John.3.16 John.3.17" are filed under the first, and books not in the registry are skipped.
+fn verse_location(element: &BytesStart) -> Result<Option<Location>, Box<dyn Error>> {
+    let osis_id = match element.try_get_attribute("osisID")? {
+        Some(attribute) => attribute.unescape_value()?.into_owned(),
+        None => match element.try_get_attribute("sID")? {
+            Some(attribute) => attribute.unescape_value()?.into_owned(),
+            None => return Ok(None),
+        },
+    };
+    let mut parts = osis_id
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .split('.');
+    let location = (|| {
+        let book = osis_book_id(parts.next()?)?;
+        let chapter = parts.next()?.parse().ok()?;
+        let verse = parts.next()?.parse().ok()?;
+        Some((book, chapter, verse))
+    })();
+    Ok(location)
+}
+
+// The marks of supplied words, <transChange type="added">, and of the words of Jesus,
+// <q who="Jesus">, or None for other changes and quotes.
+fn markup_marks(element: &BytesStart) -> Result<Option<(char, char)>, Box<dyn Error>> {
+    let (attribute, value, marks) = match element.local_name().as_ref() {
+        b"transChange" => ("type", "added", ('[', ']')),
+        _ => ("who", "Jesus", ('‹', '›')),
+    };
+    Ok(match element.try_get_attribute(attribute)? {
+        Some(found) if found.unescape_value()? == value => Some(marks),
+        _ => None,
+    })
+}
+
+fn is_heading(element: &BytesStart) -> Result<bool, Box<dyn Error>> {
+    Ok(!element
+        .try_get_attribute("type")?
+        .is_some_and(|found| SKIPPED_TITLES.contains(&found.value.as_ref())))
+}
+
+fn is_cross_reference(element: &BytesStart) -> Result<bool, Box<dyn Error>> {
+    Ok(element
+        .try_get_attribute("type")?
+        .is_some_and(|found| found.value.as_ref() == b"crossReference"))
+}
+
+// A <w> element with Strong's numbers in its lemma, ready for its text.
+fn tagged_word(element: &BytesStart, book: u8) -> Result<Option<TaggedWord>, Box<dyn Error>> {
+    let Some(lemma) = element.try_get_attribute("lemma")? else {
+        return Ok(None);
+    };
+    let strongs = StrongsNumber::parse_all(&lemma.unescape_value()?, book);
+    Ok((!strongs.is_empty()).then(|| TaggedWord {
+        text: String::new(),
+        strongs,
+    }))
+}
+
+// Takes the words and headings of the verse, which are for it even if it turns out empty.
+fn finish_verse(
+    bible: &mut Bible,
+    verse: Option<(u8, u8, u8, String)>,
+    words: &mut Vec<TaggedWord>,
+    headings: &mut Vec<String>,
+) {
+    let (words, headings) = (mem::take(words), mem::take(headings));
+    if let Some((book, chapter, verse, text)) = verse {
+        let mut verse = Verse::new(book, chapter, verse, text).with_markup();
+        if !verse.scripture.is_empty() {
+            verse.words = words;
+            verse.headings = headings;
+            bible.insert(verse);
+        }
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+    use crate::scripture::markup::{Markup, TextStyle};
+
+    #[test]
+    fn imports_container_and_milestone_verses() {
+        let bible = bible_from_str(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <osis xmlns="http://www.bibletechnologies.net/2003/OSIS/namespace">
+              <osisText osisIDWork="KJV">
+                <div type="book" osisID="John">
+                  <chapter osisID="John.3">
+                    <title type="chapter">CHAPTER 3</title>
+                    <title>Jesus and Nicodemus</title>
+                    <verse osisID="John.3.16"><w lemma="strong:G1063">For</w> <w lemma="strong:G2316">God</w> so <w lemma="strong:G25" morph="robinson:V-AAI-3S">loved</w> the world,<note>Or, only begotten</note>
+                      that he gave his <transChange type="added">only</transChange> Son.</verse>
+                    <verse sID="John.3.17" osisID="John.3.17"/>For God sent not his Son
+                    <lb/>into the world &amp; more.<verse eID="John.3.17"/>
+                  </chapter>
+                </div>
+                <div type="book" osisID="Tob">
+                  <chapter osisID="Tob.1"><verse osisID="Tob.1.1">The book of the words of Tobit.</verse></chapter>
+                </div>
+                <div type="book" osisID="1En">
+                  <chapter osisID="1En.1"><verse osisID="1En.1.1">Not a known book.</verse></chapter>
+                </div>
+              </osisText>
+            </osis>"#,
+        )
+        .unwrap();
+
+        assert_eq!(bible.len(), 3);
+        assert_eq!(
+            bible.lookup("Tobit 1:1").unwrap()[0].scripture,
+            "The book of the words of Tobit."
+        );
+        let verses = bible.lookup("John 3:16-17").unwrap();
+        assert_eq!(verses[0].reference, "John 3:16");
+        assert_eq!(verses[0].abbreviation, "Jn3:16");
+        assert_eq!(
+            verses[0].scripture,
+            "For God so loved the world, that he gave his only Son."
+        );
+        assert_eq!(
+            verses[1].scripture,
+            "For God sent not his Son into the world & more."
+        );
+        assert_eq!(verses[0].words.len(), 3);
+        assert_eq!(
+            verses[0].find_words("love")[0].strongs,
+            [StrongsNumber::Greek(25)]
+        );
+        assert!(verses[1].words.is_empty());
+        assert_eq!(
+            verses[0].render(TextStyle::Markup),
+            "For God so loved the world,{Or, only begotten} that he gave his [only] Son."
+        );
+        assert_eq!(verses[0].headings, ["Jesus and Nicodemus"]);
+        assert!(verses[1].headings.is_empty());
+    }
+
+    #[test]
+    fn imports_headings() {
+        let bible = bible_from_str(
+            r#"<osis><osisText><div type="book" osisID="Ps"><title type="main">PSALMS</title>
+              <chapter osisID="Ps.3"><title type="psalm" canonical="true">A Psalm of David, when he fled from Absalom his son.</title>
+              <verse sID="Ps.3.1" osisID="Ps.3.1"/>LORD, how are they increased that trouble me!<verse eID="Ps.3.1"/>
+              <verse sID="Ps.3.2" osisID="Ps.3.2"/>Many there be which say of my soul.<verse eID="Ps.3.2"/>
+              <title type="section">Selah<note>A pause</note></title>
+              <verse sID="Ps.3.3" osisID="Ps.3.3"/>But thou, O LORD.<verse eID="Ps.3.3"/></chapter>
+              <chapter osisID="Ps.4"><verse osisID="Ps.4.1"><title type="psalm">To the chief Musician on Neginoth.</title>Hear me when I call.</verse></chapter>
+            </div></osisText></osis>"#,
+        )
+        .unwrap();
+
+        let verses = bible.lookup("Psalm 3:1-4:1").unwrap();
+        assert_eq!(
+            verses[0].headings,
+            ["A Psalm of David, when he fled from Absalom his son."]
+        );
+        assert_eq!(
+            verses[0].scripture,
+            "LORD, how are they increased that trouble me!"
+        );
+        assert!(verses[1].headings.is_empty());
+        assert_eq!(verses[2].headings, ["Selah"]);
+        assert_eq!(verses[3].headings, ["To the chief Musician on Neginoth."]);
+        assert_eq!(verses[3].scripture, "Hear me when I call.");
+    }
+
+    #[test]
+    fn imports_markup() {
+        let bible = bible_from_str(
+            r#"<osis><osisText><div type="book" osisID="John"><chapter osisID="John.3">
+              <verse sID="John.3.5" osisID="John.3.5"/>Jesus answered, <q who="Jesus" marker="">Verily [1] I say
+              <note type="crossReference"><reference osisRef="John.1.13">John 1:13</reference></note>
+              unto thee,<verse eID="John.3.5"/>
+              <verse sID="John.3.6" osisID="John.3.6"/>That which is born of the flesh is flesh.</q>
+              He said.<verse eID="John.3.6"/>
+              <verse sID="John.3.7" osisID="John.3.7"/><q sID="q1" who="Jesus"/>Marvel not.<q eID="q1"/><verse eID="John.3.7"/>
+            </chapter></div></osisText></osis>"#,
+        )
+        .unwrap();
+
+        let verses = bible.lookup("John 3:5-7").unwrap();
+        assert_eq!(
+            verses[0].scripture,
+            "Jesus answered, Verily [1] I say unto thee,"
+        );
+        assert_eq!(verses[0].spans.len(), 1);
+        assert_eq!(
+            verses[0].render(TextStyle::Brackets),
+            "Jesus answered, ‹Verily [1] I say unto thee,›"
+        );
+        assert_eq!(
+            verses[1].render(TextStyle::Brackets),
+            "‹That which is born of the flesh is flesh.› He said."
+        );
+        assert_eq!(verses[2].spans[0].markup, Markup::WordsOfJesus);
+        assert_eq!(verses[2].render(TextStyle::Markup), "‹Marvel not.›");
+    }
+}
@@ -1,6 +1,16 @@
+use crate::scripture::books::{Book, Canon};
+use crate::scripture::markup::{self, Span, TextStyle};
+use crate::scripture::reference::{
+    book_abbreviation, book_id, book_name, Reference, ReferenceError,
+};
+use crate::scripture::scope::Scope;
+use crate::scripture::search::SearchIndex;
+use crate::scripture::strongs::TaggedWord;
+use crate::scripture::versification::Versification;
 use rand::{thread_rng, Rng};
-use regex::Regex;
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
 
 #[derive(serde::Deserialize, Debug, Clone, PartialEq)]
 pub struct Verse {
@@ -10,219 +20,716 @@ pub struct Verse {
     pub chapter: u8,
     pub verse: u8,
     pub scripture: String,
+    /// The words of the verse tagged with Strong's numbers, for translations that have them.
+    #[serde(skip)]
+    pub words: Vec<TaggedWord>,
+    /// Words of Jesus, supplied words and footnotes, for translations that mark them.
+    #[serde(skip)]
+    pub spans: Vec<Span>,
+    /// The section headings and Psalm titles before the verse, for translations that have them.
+    #[serde(skip)]
+    pub headings: Vec<String>,
+}
+
+impl Verse {
+    /// A verse with its references formatted the same way as the CSV imports, for importers
+    /// that only know the book, chapter and verse numbers.
+    pub fn new(book: u8, chapter: u8, verse: u8, scripture: String) -> Self {
+        Self {
+            reference: format!("{} {}:{}", book_name(book), chapter, verse),
+            abbreviation: format!("{}{}:{}", book_abbreviation(book), chapter, verse),
+            book,
+            chapter,
+            verse,
+            scripture,
+            words: Vec::new(),
+            spans: Vec::new(),
+            headings: Vec::new(),
+        }
+    }
+
+    /// Reads the verse's text as markup, keeping its words as the text and the rest as spans.
+    pub fn with_markup(mut self) -> Self {
+        (self.scripture, self.spans) = markup::parse(&self.scripture);
+        self
+    }
+
+    /// The verse's text with its markup shown in a text style.
+    pub fn render(&self, style: TextStyle) -> String {
+        markup::render(&self.scripture, &self.spans, style)
+    }
+
+    pub fn id(&self) -> VerseId {
+        VerseId::new(self.book, self.chapter, self.verse)
+    }
+
+    /// The tagged words that are the word or phrase asked for, ignoring case and punctuation,
+    /// or else the tagged words starting with it, so "love" finds "loved".
+    pub fn find_words(&self, word: &str) -> Vec<&TaggedWord> {
+        let simplify = |text: &str| {
+            text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase()
+        };
+        let word = simplify(word);
+        if word.is_empty() {
+            return Vec::new();
+        }
+        let words: Vec<(String, &TaggedWord)> = self
+            .words
+            .iter()
+            .map(|tagged| (format!(" {} ", simplify(&tagged.text)), tagged))
+            .collect();
+        let exact: Vec<&TaggedWord> = words
+            .iter()
+            .filter(|(text, _)| text.contains(&format!(" {} ", word)))
+            .map(|(_, tagged)| *tagged)
+            .collect();
+        if !exact.is_empty() {
+            return exact;
+        }
+        words
+            .iter()
+            .filter(|(text, _)| text.contains(&format!(" {}", word)))
+            .map(|(_, tagged)| *tagged)
+            .collect()
+    }
+}
+
+/// A verse packed into one integer as book, chapter and verse, so ids sort in canonical order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VerseId(u32);
+
+impl VerseId {
+    pub fn new(book: u8, chapter: u8, verse: u8) -> Self {
+        Self(u32::from(book) << 16 | u32::from(chapter) << 8 | u32::from(verse))
+    }
+
+    pub fn book(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    pub fn chapter(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    pub fn verse(self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Reads a stored reference such as "Song of Solomon 2:1" without going through the
+    /// reference parser, as `!next` and `!previous` do with the last verse a user read.
+    pub fn from_reference(reference: &str) -> Option<Self> {
+        let (book, location) = reference.trim().rsplit_once(' ')?;
+        let (chapter, verse) = location.split_once(':')?;
+        Some(Self::new(
+            book_id(book).ok()?,
+            chapter.parse().ok()?,
+            verse.parse().ok()?,
+        ))
+    }
+}
+
+impl fmt::Display for VerseId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}:{}",
+            book_name(self.book()),
+            self.chapter(),
+            self.verse()
+        )
+    }
 }
 
-// #[derive(Default)]
 pub struct Bible {
-    scriptures: HashMap<String, Verse>,
-    index: Vec<String>,
-    regex: Regex,
+    // Verses in canonical order, so any passage is a contiguous slice.
+    verses: Vec<Verse>,
+    positions: HashMap<VerseId, usize>,
+    // The number of verses in each chapter of each book, used to validate references.
+    chapters: HashMap<u8, Vec<u8>>,
+    search_index: SearchIndex,
+    // Set when a translation's numbering cannot be detected from its verses.
+    versification: Option<Versification>,
+}
+
+impl Default for Bible {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Bible {
     pub fn new() -> Self {
         Self {
-            scriptures: HashMap::new(),
-            index: Vec::new(),
-            regex: Regex::new(r"(?i)(\d?\s?[a-z]+\s?\d?)\s(\d+):(\d+)(?:-(\d+))?")
-                .expect("Invalid regex pattern"),
+            verses: Vec::new(),
+            positions: HashMap::new(),
+            chapters: HashMap::new(),
+            search_index: SearchIndex::new(),
+            versification: None,
         }
     }
 
+    /// A Bible from verses in canonical order and their already built search index, as stored
+    /// in the binary format.
+    pub(crate) fn from_parts(verses: Vec<Verse>, search_index: SearchIndex) -> Self {
+        let mut bible = Self {
+            search_index,
+            ..Self::new()
+        };
+        for verse in verses {
+            bible.push(verse);
+        }
+        bible
+    }
+
     pub fn insert(&mut self, scripture: Verse) {
-        self.scriptures
-            .insert(scripture.reference.clone(), scripture.clone());
-        self.index.push(scripture.reference.clone());
+        self.search_index
+            .insert(self.verses.len(), &scripture.scripture);
+        self.push(scripture);
     }
 
-    pub fn get_next_scripture(&self, current_reference: &str, verses: usize) -> Vec<Verse> {
-        self.index
-            .iter()
-            .position(|r| r == current_reference)
-            .and_then(|pos| self.index.get(pos + 1..pos + 1 + verses))
-            .map_or(Vec::new(), |references| {
-                references
-                    .iter()
-                    .flat_map(|reference| self.get_scripture(reference))
-                    .collect()
-            })
+    fn push(&mut self, scripture: Verse) {
+        let chapters = self.chapters.entry(scripture.book).or_default();
+        let chapter = usize::from(scripture.chapter);
+        if chapters.len() < chapter {
+            chapters.resize(chapter, 0);
+        }
+        if let Some(verses) = chapter.checked_sub(1).and_then(|c| chapters.get_mut(c)) {
+            *verses = (*verses).max(scripture.verse);
+        }
+
+        self.positions.insert(scripture.id(), self.verses.len());
+        self.verses.push(scripture);
     }
 
-    pub fn get_previous_scripture(&self, current_reference: &str, verses: usize) -> Vec<Verse> {
-        self.index
-            .iter()
-            .position(|r| r == current_reference)
+    /// Every verse in canonical order.
+    pub fn verses(&self) -> &[Verse] {
+        &self.verses
+    }
+
+    pub(crate) fn search_index(&self) -> &SearchIndex {
+        &self.search_index
+    }
+
+    pub fn get(&self, id: VerseId) -> Option<&Verse> {
+        self.position(id).map(|position| &self.verses[position])
+    }
+
+    /// Where a verse is stored, so its neighbours are the verses either side of it.
+    pub fn position(&self, id: VerseId) -> Option<usize> {
+        self.positions.get(&id).copied()
+    }
+
+    /// How this translation numbers its verses, detected from its chapters unless set.
+    pub fn versification(&self) -> Versification {
+        self.versification
+            .unwrap_or_else(|| Versification::detect(self))
+    }
+
+    pub fn set_versification(&mut self, versification: Versification) {
+        self.versification = Some(versification);
+    }
+
+    /// The books this translation includes, Catholic when it has any deuterocanonical book.
+    /// None of the import formats declare a canon, so it is always inferred from the books
+    /// present and is not stored.
+    pub fn canon(&self) -> Canon {
+        if self
+            .chapters
+            .keys()
+            .any(|book| !Canon::Protestant.contains(*book))
+        {
+            Canon::Catholic
+        } else {
+            Canon::Protestant
+        }
+    }
+
+    /// A reference to a verse of this translation in KJV numbering, which is how last verses are
+    /// saved so they still point at the same verse after switching translation.
+    pub fn to_kjv_reference(&self, reference: &str) -> String {
+        VerseId::from_reference(reference).map_or_else(
+            || reference.to_string(),
+            |id| self.versification().to_kjv(id).to_string(),
+        )
+    }
+
+    /// A reference in KJV numbering as it is numbered in this translation.
+    pub fn from_kjv_reference(&self, reference: &str) -> String {
+        VerseId::from_reference(reference).map_or_else(
+            || reference.to_string(),
+            |id| self.versification().from_kjv(id).to_string(),
+        )
+    }
+
+    pub fn get_next_scripture(&self, current_reference: &str, verses: usize) -> &[Verse] {
+        VerseId::from_reference(current_reference)
+            .and_then(|id| self.position(id))
             .and_then(|pos| {
-                let start_pos = if pos > verses { pos - verses } else { 0 };
-                self.index.get(start_pos..pos)
+                let end = (pos + 1 + verses).min(self.verses.len());
+                self.verses.get(pos + 1..end)
             })
-            .map_or(Vec::new(), |references| {
-                references
-                    .iter()
-                    .flat_map(|reference| self.get_scripture(reference))
-                    .collect()
+            .unwrap_or_default()
+    }
+
+    pub fn get_previous_scripture(&self, current_reference: &str, verses: usize) -> &[Verse] {
+        VerseId::from_reference(current_reference)
+            .and_then(|id| self.position(id))
+            .and_then(|pos| self.verses.get(pos.saturating_sub(verses)..pos))
+            .unwrap_or_default()
+    }
+
+    pub fn get_scripture(&self, reference: &str) -> &[Verse] {
+        self.get_scriptures(reference)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Every passage referenced in the text, in the order given, e.g. "John 3:16,18 and Rom 5:8"
+    /// returns John 3:16, John 3:18 and Romans 5:8 as three passages.
+    pub fn get_scriptures(&self, text: &str) -> Vec<&[Verse]> {
+        self.find_scriptures(text)
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// Like `get_scriptures`, but keeps the reason each reference could not be looked up.
+    pub fn find_scriptures(&self, text: &str) -> Vec<Result<&[Verse], ReferenceError>> {
+        Reference::parse_all(text)
+            .iter()
+            .map(|reference| self.get_verses(reference))
+            .collect()
+    }
+
+    /// Looks up text that is exactly one reference, e.g. "2 tim 3:16-17".
+    pub fn lookup(&self, reference: &str) -> Result<&[Verse], ReferenceError> {
+        self.get_verses(&reference.parse()?)
+    }
+
+    pub fn get_verses(&self, reference: &Reference) -> Result<&[Verse], ReferenceError> {
+        let (start, end) = (reference.start, reference.end);
+        let start_verse = start.verse.unwrap_or(1);
+        self.check_verse(start.book, start.chapter, start_verse)?;
+        // A range running past the end of its chapter stops at the last verse, as in John 3:35-40.
+        let verses = self.verse_count(end.book, end.chapter)?;
+        let end_verse = end.verse.map_or(verses, |verse| verse.min(verses));
+
+        let start = VerseId::new(start.book, start.chapter, start_verse);
+        let end = VerseId::new(end.book, end.chapter, end_verse);
+        if end < start {
+            return Err(ReferenceError::InvalidRange(reference.to_string()));
+        }
+
+        let start_position = self.position(start).ok_or_else(|| {
+            ReferenceError::NotAReference(format!(
+                "{} {}:{}",
+                book_name(start.book()),
+                start.chapter(),
+                start.verse()
+            ))
+        })?;
+        // Verses are stored in canonical order, and a translation may leave some out, so count
+        // forward to the end of the range rather than expecting its last verse to exist.
+        let length = self.verses[start_position..]
+            .iter()
+            .take_while(|verse| verse.id() <= end)
+            .count();
+        Ok(&self.verses[start_position..start_position + length])
+    }
+
+    /// The number of chapters in a book of this translation.
+    pub fn chapter_count(&self, book: u8) -> Result<u8, ReferenceError> {
+        self.chapters
+            .get(&book)
+            .map(|chapters| chapters.len() as u8)
+            .ok_or_else(|| ReferenceError::BookNotInTranslation(book_name(book).to_string()))
+    }
+
+    /// The number of verses in a chapter of this translation.
+    pub fn verse_count(&self, book: u8, chapter: u8) -> Result<u8, ReferenceError> {
+        let chapters = self.chapter_count(book)?;
+        usize::from(chapter)
+            .checked_sub(1)
+            .and_then(|c| self.chapters.get(&book)?.get(c).copied())
+            .ok_or_else(|| ReferenceError::ChapterOutOfRange {
+                book: book_name(book).to_string(),
+                chapter,
+                chapters,
             })
     }
 
-    pub fn get_scripture(&self, reference: &str) -> Vec<Verse> {
-        let mut verses = Vec::new();
-        if let Some(caps) = self.regex.captures(reference) {
-            let book_abbr = caps.get(1).map_or("", |m| m.as_str()).trim();
-            let chapter = caps.get(2).map_or("", |m| m.as_str());
-            let start_verse = caps
-                .get(3)
-                .map_or(0, |m| m.as_str().parse::<u8>().unwrap_or(0));
-            let end_verse = caps.get(4).map_or(start_verse, |m| {
-                m.as_str().parse::<u8>().unwrap_or(start_verse)
+    fn check_verse(&self, book: u8, chapter: u8, verse: u8) -> Result<(), ReferenceError> {
+        // A book missing from this translation is still checked against the canon, so John 22:1
+        // is reported as past the end of John rather than as missing.
+        if !self.chapters.contains_key(&book) {
+            if let Some(book) = Book::get(book) {
+                book.check_verse(chapter, verse)?;
+            }
+        }
+        let verses = self.verse_count(book, chapter)?;
+        if verse == 0 || verse > verses {
+            return Err(ReferenceError::VerseOutOfRange {
+                book: book_name(book).to_string(),
+                chapter,
+                verse,
+                verses,
             });
+        }
+        Ok(())
+    }
 
-            if start_verse > 0 && end_verse >= start_verse {
-                let book_name = Self::get_bible_book_name(book_abbr);
-                for verse_num in start_verse..=end_verse {
-                    let formatted_ref = format!("{} {}:{}", book_name, chapter, verse_num);
-                    if let Some(verse) = self.scriptures.get(&formatted_ref) {
-                        verses.push(verse.clone());
-                    }
-                }
-            }
+    /// Verses matching a search query, best first, e.g. `"be still" know` or `faith OR hope`.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&Verse> {
+        self.search_index
+            .search(query)
+            .into_iter()
+            .filter_map(|position| self.verses.get(position))
+            .take(limit)
+            .collect()
+    }
+
+    /// The number of verses matching a search query.
+    pub fn search_count(&self, query: &str) -> usize {
+        self.search_index.search(query).len()
+    }
+
+    pub fn random_scripture(&self) -> &[Verse] {
+        if self.verses.is_empty() {
+            return &[];
         }
-        verses
+        let position = thread_rng().gen_range(0..self.verses.len());
+        &self.verses[position..=position]
     }
 
-    pub fn random_scripture(&self) -> Vec<Verse> {
-        self.index
-            .get(thread_rng().gen_range(0..self.index.len()))
-            .and_then(|reference| self.scriptures.get(reference))
-            .map(|verse| vec![verse.clone()])
-            .unwrap_or_else(Vec::new)
+    /// A random passage from part of scripture, e.g. a verse from the Gospels or 2 to 3 verses
+    /// from Proverbs 3.  A passage stays within its chapter, so it may be shorter near the end
+    /// of a short chapter.
+    pub fn random_passage(
+        &self,
+        scope: &Scope,
+        verses: RangeInclusive<usize>,
+    ) -> Result<&[Verse], ReferenceError> {
+        let in_scope = || {
+            self.verses
+                .iter()
+                .enumerate()
+                .filter(|(_, verse)| scope.contains(verse.id()))
+        };
+        let mut rng = thread_rng();
+        let count = in_scope().count();
+        let Some((position, verse)) = (count > 0)
+            .then(|| in_scope().nth(rng.gen_range(0..count)))
+            .flatten()
+        else {
+            return Err(ReferenceError::BookNotInTranslation(scope.to_string()));
+        };
+        let length = rng.gen_range(verses).max(1);
+
+        // Grow forward, then backward if the chapter ends first.
+        let same_chapter = |other: &Verse| {
+            (other.book, other.chapter) == (verse.book, verse.chapter) && scope.contains(other.id())
+        };
+        let (mut start, mut end) = (position, position + 1);
+        while end - start < length && self.verses.get(end).is_some_and(same_chapter) {
+            end += 1;
+        }
+        while end - start < length && start > 0 && same_chapter(&self.verses[start - 1]) {
+            start -= 1;
+        }
+        Ok(&self.verses[start..end])
     }
 
     pub fn len(&self) -> usize {
-        self.scriptures.len()
+        self.verses.len()
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
-    pub fn get_bible_book_name(abbreviation: &str) -> &'static str {
-        match abbreviation.to_lowercase().as_str() {
-            "genesis" | "gen" | "ge" | "gn" => "Genesis",
-            "exodus" | "ex" | "exod" | "exo" => "Exodus",
-            "leviticus" | "lev" | "le" | "lv" => "Leviticus",
-            "numbers" | "num" | "nu" | "nm" | "nb" => "Numbers",
-            "deuteronomy" | "deut" | "de" | "dt" => "Deuteronomy",
-            "joshua" | "josh" | "jos" | "jsh" => "Joshua",
-            "judges" | "judg" | "jdg" | "jg" | "jdgs" => "Judges",
-            "ruth" | "rth" | "ru" => "Ruth",
-            "1 samuel" | "1 sam" | "1 sm" | "1 sa" | "1 s" | "i sam" | "i sa" | "1sam" | "1sa"
-            | "1s" | "1st samuel" | "1st sam" | "first samuel" | "first sam" => "1 Samuel",
-            "2 samuel" | "2 sam" | "2 sm" | "2 sa" | "ii sam" | "ii sa" | "2sam" | "2nd samuel"
-            | "2nd sam" | "second samuel" | "second sam" => "2 Samuel",
-            "1 kings" | "1 kgs" | "1 ki" | "1kgs" | "1kin" | "1ki" | "1k" | "i kgs" | "i ki"
-            | "1st kings" | "1st kgs" | "first kings" | "first kgs" => "1 Kings",
-            "2 kings" | "2 kgs" | "2 ki" | "2kgs" | "2kin" | "2ki" | "2k" | "ii kgs" | "ii ki"
-            | "2nd kings" | "2nd kgs" | "second kings" | "second kgs" => "2 Kings",
-            "1 chronicles" | "1 chron" | "1 chr" | "1 ch" | "1chron" | "1chr" | "1ch"
-            | "i chron" | "i chr" | "i ch" | "1st chronicles" | "1st chron"
-            | "first chronicles" | "first chron" => "1 Chronicles",
-            "2 chronicles" | "2 chron" | "2 chr" | "2 ch" | "2chron" | "2chr" | "2ch"
-            | "ii chron" | "ii chr" | "ii ch" | "2nd chronicles" | "2nd chron"
-            | "second chronicles" | "second chron" => "2 Chronicles",
-            "ezra" | "ezr" | "ez" => "Ezra",
-            "nehemiah" | "neh" | "ne" => "Nehemiah",
-            "esther" | "est" | "esth" | "es" => "Esther",
-            "job" | "jb" => "Job",
-            "psalm" | "psalms" | "ps" | "pslm" | "psa" | "psm" | "pss" => "Psalm",
-            "proverbs" | "prov" | "pro" | "prv" | "pr" => "Proverbs",
-            "ecclesiastes" | "eccles" | "eccle" | "ecc" | "ec" | "qoh" => "Ecclesiastes",
-            "song of solomon" | "song of songs" | "sos" => "Song of Solomon",
-            "isaiah" | "isa" | "is" => "Isaiah",
-            "jeremiah" | "jer" | "je" | "jr" => "Jeremiah",
-            "lamentations" | "lam" | "la" => "Lamentations",
-            "ezekiel" | "ezek" | "eze" | "ezk" => "Ezekiel",
-            "daniel" | "dan" | "da" | "dn" => "Daniel",
-            "hosea" | "hos" | "ho" => "Hosea",
-            "joel" | "jl" => "Joel",
-            "amos" | "am" => "Amos",
-            "obadiah" | "obad" | "ob" => "Obadiah",
-            "jonah" | "jnh" | "jon" => "Jonah",
-            "micah" | "mic" | "mc" => "Micah",
-            "nahum" | "nah" | "na" => "Nahum",
-            "habakkuk" | "hab" | "hb" => "Habakkuk",
-            "zephaniah" | "zeph" | "zep" | "zp" => "Zephaniah",
-            "haggai" | "hag" | "hg" => "Haggai",
-            "zechariah" | "zech" | "zec" | "za" => "Zechariah",
-            "malachi" | "mal" | "ml" => "Malachi",
-            "matthew" | "matt" | "mt" => "Matthew",
-            "mark" | "mrk" | "mar" | "mk" | "mr" => "Mark",
-            "luke" | "luk" | "lk" => "Luke",
-            "john" | "joh" | "jhn" | "jn" => "John",
-            "acts" | "act" | "ac" => "Acts",
-            "romans" | "rom" | "ro" | "rm" => "Romans",
-            "1 corinthians" | "1 cor" | "1 co" | "i cor" | "i co" | "1cor" | "1co"
-            | "i corinthians" | "1corinthians" | "1st corinthians" | "first corinthians" => {
-                "1 Corinthians"
-            }
-            "2 corinthians" | "2 cor" | "2 co" | "ii cor" | "ii co" | "2cor" | "2co"
-            | "ii corinthians" | "2corinthians" | "2nd corinthians" | "second corinthians" => {
-                "2 Corinthians"
-            }
-            "galatians" | "gal" | "ga" => "Galatians",
-            "ephesians" | "eph" | "ephes" => "Ephesians",
-            "philippians" | "phil" | "php" | "pp" => "Philippians",
-            "colossians" | "col" => "Colossians",
-            "1 thessalonians"
-            | "1 thess"
-            | "1 thes"
-            | "1 th"
-            | "i thess"
-            | "i thes"
-            | "i th"
-            | "1thessalonians"
-            | "1thess"
-            | "1thes"
-            | "1th"
-            | "1st thessalonians"
-            | "first thessalonians" => "1 Thessalonians",
-            "2 thessalonians"
-            | "2 thess"
-            | "2 thes"
-            | "2 th"
-            | "ii thess"
-            | "ii thes"
-            | "ii th"
-            | "2thessalonians"
-            | "2thess"
-            | "2thes"
-            | "2th"
-            | "2nd thessalonians"
-            | "second thessalonians" => "2 Thessalonians",
-            "1 timothy" | "1 tim" | "1 ti" | "i tim" | "i ti" | "1timothy" | "1tim" | "1ti"
-            | "1st timothy" | "first timothy" => "1 Timothy",
-            "2 timothy" | "2 tim" | "2 ti" | "ii tim" | "ii ti" | "2timothy" | "2tim" | "2ti"
-            | "2nd timothy" | "second timothy" => "2 Timothy",
-            "titus" | "tit" | "ti" => "Titus",
-            "philemon" | "philem" | "phm" | "pm" => "Philemon",
-            "hebrews" | "heb" => "Hebrews",
-            "james" | "jas" | "jm" => "James",
-            "1 peter" | "1 pet" | "1 pe" | "1 pt" | "1 p" | "i pet" | "i pe" | "i pt"
-            | "1peter" | "1pet" | "1pe" | "1pt" | "1p" | "1st peter" | "first peter" => "1 Peter",
-            "2 peter" | "2 pet" | "2 pe" | "2 pt" | "2 p" | "ii pet" | "ii pe" | "ii pt"
-            | "2peter" | "2pet" | "2pe" | "2pt" | "2p" | "2nd peter" | "second peter" => "2 Peter",
-            "1 john" | "1 jhn" | "1 jn" | "1 j" | "1john" | "1jhn" | "1joh" | "1jn" | "1jo"
-            | "1j" | "i john" | "i jhn" | "i joh" | "i jn" | "i jo" | "1st john" | "first john" => {
-                "1 John"
+
+    /// Whether any verse starts a section with a heading.
+    pub fn has_headings(&self) -> bool {
+        self.verses.iter().any(|verse| !verse.headings.is_empty())
+    }
+
+    /// Whether any verse has words tagged with Strong's numbers.
+    pub fn has_strongs(&self) -> bool {
+        self.verses.iter().any(|verse| !verse.words.is_empty())
+    }
+
+    /// The canonical name for any recognised book name or abbreviation, e.g. "jn" is John.
+    pub fn get_bible_book_name(abbreviation: &str) -> Result<&'static str, ReferenceError> {
+        Book::find(abbreviation)
+            .map(|book| book.name)
+            .ok_or_else(|| ReferenceError::UnknownBook(abbreviation.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    fn verse(book: u8, book_name: &str, abbreviation: &str, chapter: u8, verse: u8) -> Verse {
+        Verse {
+            reference: format!("{} {}:{}", book_name, chapter, verse),
+            abbreviation: format!("{}{}:{}", abbreviation, chapter, verse),
+            book,
+            chapter,
+            verse,
+            scripture: format!("{} {}:{} text", book_name, chapter, verse),
+            words: Vec::new(),
+            spans: Vec::new(),
+            headings: Vec::new(),
+        }
+    }
+
+    fn sample_bible() -> Bible {
+        let mut bible = Bible::new();
+        for (book, name, abbreviation, chapters) in [
+            (39, "Malachi", "Mal", 4),
+            (40, "Matthew", "Mt", 2),
+            (43, "John", "Jn", 4),
+        ] {
+            for chapter in 1..=chapters {
+                for number in 1..=5 {
+                    bible.insert(verse(book, name, abbreviation, chapter, number));
+                }
             }
-            "2 john" | "2 jhn" | "2 jn" | "2 j" | "2john" | "2jhn" | "2joh" | "2jn" | "2jo"
-            | "2j" | "ii john" | "ii jhn" | "ii joh" | "ii jn" | "ii jo" | "2nd john"
-            | "second john" => "2 John",
-            "3 john" | "3 jhn" | "3 jn" | "3 j" | "3john" | "3jhn" | "3joh" | "3jn" | "3jo"
-            | "3j" | "iii john" | "iii jhn" | "iii joh" | "iii jn" | "iii jo" | "3rd john"
-            | "third john" => "3 John",
-            "jude" | "jud" | "jd" => "Jude",
-            "revelation" | "rev" | "re" => "Revelation",
-            // Default case
-            _ => "Unknown Book",
         }
+        bible
+    }
+
+    fn references(verses: &[Verse]) -> Vec<&str> {
+        verses
+            .iter()
+            .map(|verse| verse.reference.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn get_scripture_within_chapter() {
+        let bible = sample_bible();
+        assert_eq!(references(bible.get_scripture("jn 3:2")), ["John 3:2"]);
+        assert_eq!(
+            references(bible.get_scripture("John 3:2-4")),
+            ["John 3:2", "John 3:3", "John 3:4"]
+        );
+        assert_eq!(
+            references(bible.get_scripture("John 3:4-9")),
+            ["John 3:4", "John 3:5"]
+        );
+        assert!(bible.get_scripture("John 3:4-2").is_empty());
+    }
+
+    #[test]
+    fn get_scripture_across_chapters() {
+        let bible = sample_bible();
+        assert_eq!(
+            references(bible.get_scripture("John 3:4-4:2")),
+            ["John 3:4", "John 3:5", "John 4:1", "John 4:2"]
+        );
+        assert_eq!(
+            references(bible.get_scripture("John 2:5 - 3:1")),
+            ["John 2:5", "John 3:1"]
+        );
+        assert!(bible.get_scripture("John 4:2-3:4").is_empty());
+    }
+
+    #[test]
+    fn get_scripture_across_books() {
+        let bible = sample_bible();
+        assert_eq!(
+            references(bible.get_scripture("Mal 4:4-Matt 1:2")),
+            ["Malachi 4:4", "Malachi 4:5", "Matthew 1:1", "Matthew 1:2"]
+        );
+        assert!(bible.get_scripture("Matt 1:1-Mal 4:4").is_empty());
+        // A range only ends in a known book, so this is read as Malachi 4:4 alone.
+        assert_eq!(
+            references(bible.get_scripture("Mal 4:4-Hezekiah 1:2")),
+            ["Malachi 4:4"]
+        );
+    }
+
+    #[test]
+    fn get_scripture_whole_chapters() {
+        let bible = sample_bible();
+        assert_eq!(bible.get_scripture("John 3").len(), 5);
+        assert_eq!(bible.get_scripture("John 2-3").len(), 10);
+        assert_eq!(bible.get_scripture("John 3-4:2").len(), 7);
+        assert_eq!(bible.get_scripture("Mal 4-Matt 1").len(), 10);
+        assert!(bible.get_scripture("John 9").is_empty());
+        assert!(bible.get_scripture("jn 3").is_empty());
+    }
+
+    #[test]
+    fn get_scripture_skips_unknown_matches() {
+        let bible = sample_bible();
+        assert_eq!(
+            references(bible.get_scripture("I have 2 questions about john 3:2 today")),
+            ["John 3:2"]
+        );
+        assert!(bible.get_scripture("see you in 5 minutes").is_empty());
+    }
+
+    #[test]
+    fn get_scriptures_multiple_references() {
+        let bible = sample_bible();
+        let passages = bible.get_scriptures("John 3:1, Mal 4:5 and Matt 2:1-2");
+        let passages: Vec<Vec<&str>> = passages.iter().map(|p| references(p)).collect();
+        assert_eq!(
+            passages,
+            [
+                vec!["John 3:1"],
+                vec!["Malachi 4:5"],
+                vec!["Matthew 2:1", "Matthew 2:2"]
+            ]
+        );
+    }
+
+    #[test]
+    fn get_scriptures_verse_lists() {
+        let bible = sample_bible();
+        let passages = bible.get_scriptures("John 3:1,3, 4-5; 4:2");
+        let passages: Vec<Vec<&str>> = passages.iter().map(|p| references(p)).collect();
+        assert_eq!(
+            passages,
+            [
+                vec!["John 3:1"],
+                vec!["John 3:3"],
+                vec!["John 3:4", "John 3:5"],
+                vec!["John 4:2"]
+            ]
+        );
+        assert_eq!(bible.get_scriptures("John 2, 4").len(), 2);
+        assert_eq!(bible.get_scriptures("John 2, 4")[1].len(), 5);
+        // 1 John is not in the sample, and its "1" must not be read as John 3:1.
+        assert_eq!(bible.get_scriptures("John 3:1, 1 John 1:1").len(), 1);
+    }
+
+    #[test]
+    fn next_and_previous_scripture() {
+        let bible = sample_bible();
+        assert_eq!(
+            references(bible.get_next_scripture("Malachi 4:4", 3)),
+            ["Malachi 4:5", "Matthew 1:1", "Matthew 1:2"]
+        );
+        assert_eq!(
+            references(bible.get_previous_scripture("John 2:2", 3)),
+            ["John 1:4", "John 1:5", "John 2:1"]
+        );
+        assert_eq!(
+            references(bible.get_previous_scripture("Malachi 1:2", 3)),
+            ["Malachi 1:1"]
+        );
+        assert_eq!(
+            references(bible.get_next_scripture("John 4:4", 3)),
+            ["John 4:5"]
+        );
+        assert!(bible.get_next_scripture("John 4:5", 1).is_empty());
+        assert!(bible.get_next_scripture("Acts 1:1", 1).is_empty());
+        assert_eq!(
+            bible
+                .get(VerseId::new(43, 3, 2))
+                .map(|verse| verse.reference.as_str()),
+            Some("John 3:2")
+        );
+    }
+
+    #[test]
+    fn kjv_references() {
+        let mut bible = sample_bible();
+        assert_eq!(bible.versification(), Versification::Kjv);
+        assert_eq!(bible.to_kjv_reference("Malachi 4:5"), "Malachi 4:5");
+
+        bible.set_versification(Versification::Hebrew);
+        assert_eq!(bible.to_kjv_reference("Malachi 3:23"), "Malachi 4:5");
+        assert_eq!(bible.from_kjv_reference("Malachi 4:5"), "Malachi 3:23");
+        assert_eq!(bible.from_kjv_reference("not a verse"), "not a verse");
+    }
+
+    #[test]
+    fn lookup_errors() {
+        let bible = sample_bible();
+        assert_eq!(references(bible.lookup("jn 3:2").unwrap()), ["John 3:2"]);
+        assert_eq!(
+            bible.lookup("Hezekiah 1:1").unwrap_err().to_string(),
+            "Hezekiah is not a book of the Bible"
+        );
+        assert_eq!(
+            bible.lookup("John 9:1").unwrap_err().to_string(),
+            "John only has 4 chapters"
+        );
+        assert_eq!(
+            bible.lookup("John 3:9").unwrap_err().to_string(),
+            "John 3 only has 5 verses"
+        );
+        assert_eq!(
+            bible.lookup("Acts 1:1").unwrap_err(),
+            ReferenceError::BookNotInTranslation("Acts".to_string())
+        );
+        assert_eq!(
+            bible.lookup("John 3:4-2").unwrap_err().to_string(),
+            "John 3:4-2 ends before it starts"
+        );
+    }
+
+    #[test]
+    fn random_passages_stay_in_scope() {
+        let bible = sample_bible();
+        for _ in 0..20 {
+            let verses = bible.random_passage(&Scope::Chapter(43, 2), 1..=1).unwrap();
+            assert_eq!(verses.len(), 1);
+            assert_eq!((verses[0].book, verses[0].chapter), (43, 2));
+
+            let passage = bible
+                .random_passage(&"gospels".parse().unwrap(), 2..=3)
+                .unwrap();
+            assert!((2..=3).contains(&passage.len()));
+            assert!(passage
+                .iter()
+                .all(|verse| verse.book == 40 || verse.book == 43));
+            assert!(passage
+                .windows(2)
+                .all(|pair| pair[0].chapter == pair[1].chapter));
+        }
+        assert_eq!(
+            bible
+                .random_passage(&"nt".parse().unwrap(), 1..=1)
+                .map(|verses| verses[0].book > 39),
+            Ok(true)
+        );
+        assert_eq!(
+            bible
+                .random_passage(&"romans".parse().unwrap(), 1..=1)
+                .unwrap_err()
+                .to_string(),
+            "Romans is not in this translation"
+        );
+    }
+
+    #[test]
+    fn canon_is_detected() {
+        let mut bible = sample_bible();
+        assert_eq!(bible.canon(), Canon::Protestant);
+        assert_eq!(
+            bible.lookup("Sirach 2:1").unwrap_err().to_string(),
+            "Sirach is not in this translation"
+        );
+        assert_eq!(
+            bible.lookup("Tobit 15:1").unwrap_err().to_string(),
+            "Tobit only has 14 chapters"
+        );
+
+        bible.insert(Verse::new(76, 1, 1, "And it happened".to_string()));
+        assert_eq!(bible.canon(), Canon::Catholic);
+        assert_eq!(
+            references(bible.lookup("1 Macc 1:1").unwrap()),
+            ["1 Maccabees 1:1"]
+        );
     }
 }
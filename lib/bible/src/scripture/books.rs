@@ -0,0 +1,1750 @@
+use crate::scripture::reference::ReferenceError;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The languages book names are recognised in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    Spanish,
+    German,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Testament {
+    Old,
+    New,
+}
+
+/// The books a translation includes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Canon {
+    /// The 66 books from Genesis to Revelation.
+    Protestant,
+    /// The Protestant books and the deuterocanon, as in Catholic and Orthodox Bibles.
+    Catholic,
+}
+
+/// What is known about a book regardless of translation.  Chapter and verse counts follow the KJV.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Book {
+    /// The position in the registry, Genesis is 1, Revelation 66 and Tobit 67.
+    pub id: u8,
+    pub name: &'static str,
+    /// The short name used in abbreviated references, e.g. Jn3:16.
+    pub abbreviation: &'static str,
+    /// The code used in the osisID of OSIS XML files, e.g. John.3.16.
+    pub osis: &'static str,
+    /// The code used in the `\id` line of USFM files, e.g. JHN.
+    pub usfm: &'static str,
+    pub testament: Testament,
+    /// The number of verses in each chapter.
+    pub verses: &'static [u8],
+    /// The lowercase names and abbreviations a reference may use for the book.
+    pub names: &'static [&'static str],
+}
+
+/// Every book at its id minus one: the 66 books of the Protestant canon in order, then the
+/// deuterocanonical books of Catholic and Orthodox Bibles.  Additions to Esther and the three
+/// additions to Daniel are books of their own, numbered as in the KJV Apocrypha.
+pub static BOOKS: [Book; 77] = [
+    Book {
+        id: 1,
+        name: "Genesis",
+        abbreviation: "Gn",
+        osis: "Gen",
+        usfm: "GEN",
+        testament: Testament::Old,
+        verses: &[
+            31, 25, 24, 26, 32, 22, 24, 22, 29, 32, 32, 20, 18, 24, 21, 16, 27, 33, 38, 18, 34, 24,
+            20, 67, 34, 35, 46, 22, 35, 43, 55, 32, 20, 31, 29, 43, 36, 30, 23, 23, 57, 38, 34, 34,
+            28, 34, 31, 22, 33, 26,
+        ],
+        names: &["genesis", "gen", "ge", "gn"],
+    },
+    Book {
+        id: 2,
+        name: "Exodus",
+        abbreviation: "Ex",
+        osis: "Exod",
+        usfm: "EXO",
+        testament: Testament::Old,
+        verses: &[
+            22, 25, 22, 31, 23, 30, 25, 32, 35, 29, 10, 51, 22, 31, 27, 36, 16, 27, 25, 26, 36, 31,
+            33, 18, 40, 37, 21, 43, 46, 38, 18, 35, 23, 35, 35, 38, 29, 31, 43, 38,
+        ],
+        names: &["exodus", "ex", "exod", "exo"],
+    },
+    Book {
+        id: 3,
+        name: "Leviticus",
+        abbreviation: "Lv",
+        osis: "Lev",
+        usfm: "LEV",
+        testament: Testament::Old,
+        verses: &[
+            17, 16, 17, 35, 19, 30, 38, 36, 24, 20, 47, 8, 59, 57, 33, 34, 16, 30, 37, 27, 24, 33,
+            44, 23, 55, 46, 34,
+        ],
+        names: &["leviticus", "lev", "le", "lv"],
+    },
+    Book {
+        id: 4,
+        name: "Numbers",
+        abbreviation: "Nm",
+        osis: "Num",
+        usfm: "NUM",
+        testament: Testament::Old,
+        verses: &[
+            54, 34, 51, 49, 31, 27, 89, 26, 23, 36, 35, 16, 33, 45, 41, 50, 13, 32, 22, 29, 35, 41,
+            30, 25, 18, 65, 23, 31, 40, 16, 54, 42, 56, 29, 34, 13,
+        ],
+        names: &["numbers", "num", "nu", "nm", "nb"],
+    },
+    Book {
+        id: 5,
+        name: "Deuteronomy",
+        abbreviation: "Dt",
+        osis: "Deut",
+        usfm: "DEU",
+        testament: Testament::Old,
+        verses: &[
+            46, 37, 29, 49, 33, 25, 26, 20, 29, 22, 32, 32, 18, 29, 23, 22, 20, 22, 21, 20, 23, 30,
+            25, 22, 19, 19, 26, 68, 29, 20, 30, 52, 29, 12,
+        ],
+        names: &["deuteronomy", "deut", "de", "dt"],
+    },
+    Book {
+        id: 6,
+        name: "Joshua",
+        abbreviation: "Jos",
+        osis: "Josh",
+        usfm: "JOS",
+        testament: Testament::Old,
+        verses: &[
+            18, 24, 17, 24, 15, 27, 26, 35, 27, 43, 23, 24, 33, 15, 63, 10, 18, 28, 51, 9, 45, 34,
+            16, 33,
+        ],
+        names: &["joshua", "josh", "jos", "jsh"],
+    },
+    Book {
+        id: 7,
+        name: "Judges",
+        abbreviation: "Jdg",
+        osis: "Judg",
+        usfm: "JDG",
+        testament: Testament::Old,
+        verses: &[
+            36, 23, 31, 24, 31, 40, 25, 35, 57, 18, 40, 15, 25, 20, 20, 31, 13, 31, 30, 48, 25,
+        ],
+        names: &["judges", "judg", "jdg", "jg", "jdgs"],
+    },
+    Book {
+        id: 8,
+        name: "Ruth",
+        abbreviation: "Rt",
+        osis: "Ruth",
+        usfm: "RUT",
+        testament: Testament::Old,
+        verses: &[22, 23, 18, 22],
+        names: &["ruth", "rth", "ru"],
+    },
+    Book {
+        id: 9,
+        name: "1 Samuel",
+        abbreviation: "1Sm",
+        osis: "1Sam",
+        usfm: "1SA",
+        testament: Testament::Old,
+        verses: &[
+            28, 36, 21, 22, 12, 21, 17, 22, 27, 27, 15, 25, 23, 52, 35, 23, 58, 30, 24, 42, 15, 23,
+            29, 22, 44, 25, 12, 25, 11, 31, 13,
+        ],
+        names: &[
+            "1 samuel",
+            "1 sam",
+            "1 sm",
+            "1 sa",
+            "1 s",
+            "i sam",
+            "i sa",
+            "1sam",
+            "1sa",
+            "1s",
+            "1st samuel",
+            "1st sam",
+            "first samuel",
+            "first sam",
+        ],
+    },
+    Book {
+        id: 10,
+        name: "2 Samuel",
+        abbreviation: "2Sm",
+        osis: "2Sam",
+        usfm: "2SA",
+        testament: Testament::Old,
+        verses: &[
+            27, 32, 39, 12, 25, 23, 29, 18, 13, 19, 27, 31, 39, 33, 37, 23, 29, 33, 43, 26, 22, 51,
+            39, 25,
+        ],
+        names: &[
+            "2 samuel",
+            "2 sam",
+            "2 sm",
+            "2 sa",
+            "ii sam",
+            "ii sa",
+            "2sam",
+            "2nd samuel",
+            "2nd sam",
+            "second samuel",
+            "second sam",
+        ],
+    },
+    Book {
+        id: 11,
+        name: "1 Kings",
+        abbreviation: "1Kg",
+        osis: "1Kgs",
+        usfm: "1KI",
+        testament: Testament::Old,
+        verses: &[
+            53, 46, 28, 34, 18, 38, 51, 66, 28, 29, 43, 33, 34, 31, 34, 34, 24, 46, 21, 43, 29, 53,
+        ],
+        names: &[
+            "1 kings",
+            "1 kgs",
+            "1 ki",
+            "1kgs",
+            "1kin",
+            "1ki",
+            "1k",
+            "i kgs",
+            "i ki",
+            "1st kings",
+            "1st kgs",
+            "first kings",
+            "first kgs",
+        ],
+    },
+    Book {
+        id: 12,
+        name: "2 Kings",
+        abbreviation: "2Kg",
+        osis: "2Kgs",
+        usfm: "2KI",
+        testament: Testament::Old,
+        verses: &[
+            18, 25, 27, 44, 27, 33, 20, 29, 37, 36, 21, 21, 25, 29, 38, 20, 41, 37, 37, 21, 26, 20,
+            37, 20, 30,
+        ],
+        names: &[
+            "2 kings",
+            "2 kgs",
+            "2 ki",
+            "2kgs",
+            "2kin",
+            "2ki",
+            "2k",
+            "ii kgs",
+            "ii ki",
+            "2nd kings",
+            "2nd kgs",
+            "second kings",
+            "second kgs",
+        ],
+    },
+    Book {
+        id: 13,
+        name: "1 Chronicles",
+        abbreviation: "1Ch",
+        osis: "1Chr",
+        usfm: "1CH",
+        testament: Testament::Old,
+        verses: &[
+            54, 55, 24, 43, 26, 81, 40, 40, 44, 14, 47, 40, 14, 17, 29, 43, 27, 17, 19, 8, 30, 19,
+            32, 31, 31, 32, 34, 21, 30,
+        ],
+        names: &[
+            "1 chronicles",
+            "1 chron",
+            "1 chr",
+            "1 ch",
+            "1chron",
+            "1chr",
+            "1ch",
+            "i chron",
+            "i chr",
+            "i ch",
+            "1st chronicles",
+            "1st chron",
+            "first chronicles",
+            "first chron",
+        ],
+    },
+    Book {
+        id: 14,
+        name: "2 Chronicles",
+        abbreviation: "2Ch",
+        osis: "2Chr",
+        usfm: "2CH",
+        testament: Testament::Old,
+        verses: &[
+            17, 18, 17, 22, 14, 42, 22, 18, 31, 19, 23, 16, 22, 15, 19, 14, 19, 34, 11, 37, 20, 12,
+            21, 27, 28, 23, 9, 27, 36, 27, 21, 33, 25, 33, 27, 23,
+        ],
+        names: &[
+            "2 chronicles",
+            "2 chron",
+            "2 chr",
+            "2 ch",
+            "2chron",
+            "2chr",
+            "2ch",
+            "ii chron",
+            "ii chr",
+            "ii ch",
+            "2nd chronicles",
+            "2nd chron",
+            "second chronicles",
+            "second chron",
+        ],
+    },
+    Book {
+        id: 15,
+        name: "Ezra",
+        abbreviation: "Ezr",
+        osis: "Ezra",
+        usfm: "EZR",
+        testament: Testament::Old,
+        verses: &[11, 70, 13, 24, 17, 22, 28, 36, 15, 44],
+        names: &["ezra", "ezr", "ez"],
+    },
+    Book {
+        id: 16,
+        name: "Nehemiah",
+        abbreviation: "Neh",
+        osis: "Neh",
+        usfm: "NEH",
+        testament: Testament::Old,
+        verses: &[11, 20, 32, 23, 19, 19, 73, 18, 38, 39, 36, 47, 31],
+        names: &["nehemiah", "neh", "ne"],
+    },
+    Book {
+        id: 17,
+        name: "Esther",
+        abbreviation: "Est",
+        osis: "Esth",
+        usfm: "EST",
+        testament: Testament::Old,
+        verses: &[22, 23, 15, 17, 14, 14, 10, 17, 32, 3],
+        names: &["esther", "est", "esth", "es"],
+    },
+    Book {
+        id: 18,
+        name: "Job",
+        abbreviation: "Jb",
+        osis: "Job",
+        usfm: "JOB",
+        testament: Testament::Old,
+        verses: &[
+            22, 13, 26, 21, 27, 30, 21, 22, 35, 22, 20, 25, 28, 22, 35, 22, 16, 21, 29, 29, 34, 30,
+            17, 25, 6, 14, 23, 28, 25, 31, 40, 22, 33, 37, 16, 33, 24, 41, 30, 24, 34, 17,
+        ],
+        names: &["job", "jb"],
+    },
+    Book {
+        id: 19,
+        name: "Psalm",
+        abbreviation: "Ps",
+        osis: "Ps",
+        usfm: "PSA",
+        testament: Testament::Old,
+        verses: &[
+            6, 12, 8, 8, 12, 10, 17, 9, 20, 18, 7, 8, 6, 7, 5, 11, 15, 50, 14, 9, 13, 31, 6, 10,
+            22, 12, 14, 9, 11, 12, 24, 11, 22, 22, 28, 12, 40, 22, 13, 17, 13, 11, 5, 26, 17, 11,
+            9, 14, 20, 23, 19, 9, 6, 7, 23, 13, 11, 11, 17, 12, 8, 12, 11, 10, 13, 20, 7, 35, 36,
+            5, 24, 20, 28, 23, 10, 12, 20, 72, 13, 19, 16, 8, 18, 12, 13, 17, 7, 18, 52, 17, 16,
+            15, 5, 23, 11, 13, 12, 9, 9, 5, 8, 28, 22, 35, 45, 48, 43, 13, 31, 7, 10, 10, 9, 8, 18,
+            19, 2, 29, 176, 7, 8, 9, 4, 8, 5, 6, 5, 6, 8, 8, 3, 18, 3, 3, 21, 26, 9, 8, 24, 13, 10,
+            7, 12, 15, 21, 10, 20, 14, 9, 6,
+        ],
+        names: &["psalm", "psalms", "ps", "pslm", "psa", "psm", "pss"],
+    },
+    Book {
+        id: 20,
+        name: "Proverbs",
+        abbreviation: "Prv",
+        osis: "Prov",
+        usfm: "PRO",
+        testament: Testament::Old,
+        verses: &[
+            33, 22, 35, 27, 23, 35, 27, 36, 18, 32, 31, 28, 25, 35, 33, 33, 28, 24, 29, 30, 31, 29,
+            35, 34, 28, 28, 27, 28, 27, 33, 31,
+        ],
+        names: &["proverbs", "prov", "pro", "prv", "pr"],
+    },
+    Book {
+        id: 21,
+        name: "Ecclesiastes",
+        abbreviation: "Ecc",
+        osis: "Eccl",
+        usfm: "ECC",
+        testament: Testament::Old,
+        verses: &[18, 26, 22, 16, 20, 12, 29, 17, 18, 20, 10, 14],
+        names: &["ecclesiastes", "eccles", "eccle", "ecc", "ec", "qoh"],
+    },
+    Book {
+        id: 22,
+        name: "Song of Solomon",
+        abbreviation: "SoS",
+        osis: "Song",
+        usfm: "SNG",
+        testament: Testament::Old,
+        verses: &[17, 17, 11, 16, 16, 13, 13, 14],
+        names: &["song of solomon", "song of songs", "sos"],
+    },
+    Book {
+        id: 23,
+        name: "Isaiah",
+        abbreviation: "Is",
+        osis: "Isa",
+        usfm: "ISA",
+        testament: Testament::Old,
+        verses: &[
+            31, 22, 26, 6, 30, 13, 25, 22, 21, 34, 16, 6, 22, 32, 9, 14, 14, 7, 25, 6, 17, 25, 18,
+            23, 12, 21, 13, 29, 24, 33, 9, 20, 24, 17, 10, 22, 38, 22, 8, 31, 29, 25, 28, 28, 25,
+            13, 15, 22, 26, 11, 23, 15, 12, 17, 13, 12, 21, 14, 21, 22, 11, 12, 19, 12, 25, 24,
+        ],
+        names: &["isaiah", "isa", "is"],
+    },
+    Book {
+        id: 24,
+        name: "Jeremiah",
+        abbreviation: "Jer",
+        osis: "Jer",
+        usfm: "JER",
+        testament: Testament::Old,
+        verses: &[
+            19, 37, 25, 31, 31, 30, 34, 22, 26, 25, 23, 17, 27, 22, 21, 21, 27, 23, 15, 18, 14, 30,
+            40, 10, 38, 24, 22, 17, 32, 24, 40, 44, 26, 22, 19, 32, 21, 28, 18, 16, 18, 22, 13, 30,
+            5, 28, 7, 47, 39, 46, 64, 34,
+        ],
+        names: &["jeremiah", "jer", "je", "jr"],
+    },
+    Book {
+        id: 25,
+        name: "Lamentations",
+        abbreviation: "Lm",
+        osis: "Lam",
+        usfm: "LAM",
+        testament: Testament::Old,
+        verses: &[22, 22, 66, 22, 22],
+        names: &["lamentations", "lam", "la"],
+    },
+    Book {
+        id: 26,
+        name: "Ezekiel",
+        abbreviation: "Ezk",
+        osis: "Ezek",
+        usfm: "EZK",
+        testament: Testament::Old,
+        verses: &[
+            28, 10, 27, 17, 17, 14, 27, 18, 11, 22, 25, 28, 23, 23, 8, 63, 24, 32, 14, 49, 32, 31,
+            49, 27, 17, 21, 36, 26, 21, 26, 18, 32, 33, 31, 15, 38, 28, 23, 29, 49, 26, 20, 27, 31,
+            25, 24, 23, 35,
+        ],
+        names: &["ezekiel", "ezek", "eze", "ezk"],
+    },
+    Book {
+        id: 27,
+        name: "Daniel",
+        abbreviation: "Dn",
+        osis: "Dan",
+        usfm: "DAN",
+        testament: Testament::Old,
+        verses: &[21, 49, 30, 37, 31, 28, 28, 27, 27, 21, 45, 13],
+        names: &["daniel", "dan", "da", "dn"],
+    },
+    Book {
+        id: 28,
+        name: "Hosea",
+        abbreviation: "Hos",
+        osis: "Hos",
+        usfm: "HOS",
+        testament: Testament::Old,
+        verses: &[11, 23, 5, 19, 15, 11, 16, 14, 17, 15, 12, 14, 16, 9],
+        names: &["hosea", "hos", "ho"],
+    },
+    Book {
+        id: 29,
+        name: "Joel",
+        abbreviation: "Jl",
+        osis: "Joel",
+        usfm: "JOL",
+        testament: Testament::Old,
+        verses: &[20, 32, 21],
+        names: &["joel", "jl"],
+    },
+    Book {
+        id: 30,
+        name: "Amos",
+        abbreviation: "Am",
+        osis: "Amos",
+        usfm: "AMO",
+        testament: Testament::Old,
+        verses: &[15, 16, 15, 13, 27, 14, 17, 14, 15],
+        names: &["amos", "am"],
+    },
+    Book {
+        id: 31,
+        name: "Obadiah",
+        abbreviation: "Ob",
+        osis: "Obad",
+        usfm: "OBA",
+        testament: Testament::Old,
+        verses: &[21],
+        names: &["obadiah", "obad", "ob"],
+    },
+    Book {
+        id: 32,
+        name: "Jonah",
+        abbreviation: "Jon",
+        osis: "Jonah",
+        usfm: "JON",
+        testament: Testament::Old,
+        verses: &[17, 10, 10, 11],
+        names: &["jonah", "jnh", "jon"],
+    },
+    Book {
+        id: 33,
+        name: "Micah",
+        abbreviation: "Mic",
+        osis: "Mic",
+        usfm: "MIC",
+        testament: Testament::Old,
+        verses: &[16, 13, 12, 13, 15, 16, 20],
+        names: &["micah", "mic", "mc"],
+    },
+    Book {
+        id: 34,
+        name: "Nahum",
+        abbreviation: "Nah",
+        osis: "Nah",
+        usfm: "NAM",
+        testament: Testament::Old,
+        verses: &[15, 13, 19],
+        names: &["nahum", "nah", "na"],
+    },
+    Book {
+        id: 35,
+        name: "Habakkuk",
+        abbreviation: "Hab",
+        osis: "Hab",
+        usfm: "HAB",
+        testament: Testament::Old,
+        verses: &[17, 20, 19],
+        names: &["habakkuk", "hab", "hb"],
+    },
+    Book {
+        id: 36,
+        name: "Zephaniah",
+        abbreviation: "Zep",
+        osis: "Zeph",
+        usfm: "ZEP",
+        testament: Testament::Old,
+        verses: &[18, 15, 20],
+        names: &["zephaniah", "zeph", "zep", "zp"],
+    },
+    Book {
+        id: 37,
+        name: "Haggai",
+        abbreviation: "Hag",
+        osis: "Hag",
+        usfm: "HAG",
+        testament: Testament::Old,
+        verses: &[15, 23],
+        names: &["haggai", "hag", "hg"],
+    },
+    Book {
+        id: 38,
+        name: "Zechariah",
+        abbreviation: "Zec",
+        osis: "Zech",
+        usfm: "ZEC",
+        testament: Testament::Old,
+        verses: &[21, 13, 10, 14, 11, 15, 14, 23, 17, 12, 17, 14, 9, 21],
+        names: &["zechariah", "zech", "zec", "za"],
+    },
+    Book {
+        id: 39,
+        name: "Malachi",
+        abbreviation: "Mal",
+        osis: "Mal",
+        usfm: "MAL",
+        testament: Testament::Old,
+        verses: &[14, 17, 18, 6],
+        names: &["malachi", "mal", "ml"],
+    },
+    Book {
+        id: 40,
+        name: "Matthew",
+        abbreviation: "Mt",
+        osis: "Matt",
+        usfm: "MAT",
+        testament: Testament::New,
+        verses: &[
+            25, 23, 17, 25, 48, 34, 29, 34, 38, 42, 30, 50, 58, 36, 39, 28, 27, 35, 30, 34, 46, 46,
+            39, 51, 46, 75, 66, 20,
+        ],
+        names: &["matthew", "matt", "mt"],
+    },
+    Book {
+        id: 41,
+        name: "Mark",
+        abbreviation: "Mk",
+        osis: "Mark",
+        usfm: "MRK",
+        testament: Testament::New,
+        verses: &[
+            45, 28, 35, 41, 43, 56, 37, 38, 50, 52, 33, 44, 37, 72, 47, 20,
+        ],
+        names: &["mark", "mrk", "mar", "mk", "mr"],
+    },
+    Book {
+        id: 42,
+        name: "Luke",
+        abbreviation: "Lk",
+        osis: "Luke",
+        usfm: "LUK",
+        testament: Testament::New,
+        verses: &[
+            80, 52, 38, 44, 39, 49, 50, 56, 62, 42, 54, 59, 35, 35, 32, 31, 37, 43, 48, 47, 38, 71,
+            56, 53,
+        ],
+        names: &["luke", "luk", "lk"],
+    },
+    Book {
+        id: 43,
+        name: "John",
+        abbreviation: "Jn",
+        osis: "John",
+        usfm: "JHN",
+        testament: Testament::New,
+        verses: &[
+            51, 25, 36, 54, 47, 71, 53, 59, 41, 42, 57, 50, 38, 31, 27, 33, 26, 40, 42, 31, 25,
+        ],
+        names: &["john", "joh", "jhn", "jn"],
+    },
+    Book {
+        id: 44,
+        name: "Acts",
+        abbreviation: "Ac",
+        osis: "Acts",
+        usfm: "ACT",
+        testament: Testament::New,
+        verses: &[
+            26, 47, 26, 37, 42, 15, 60, 40, 43, 48, 30, 25, 52, 28, 41, 40, 34, 28, 41, 38, 40, 30,
+            35, 27, 27, 32, 44, 31,
+        ],
+        names: &["acts", "act", "ac"],
+    },
+    Book {
+        id: 45,
+        name: "Romans",
+        abbreviation: "Rm",
+        osis: "Rom",
+        usfm: "ROM",
+        testament: Testament::New,
+        verses: &[
+            32, 29, 31, 25, 21, 23, 25, 39, 33, 21, 36, 21, 14, 23, 33, 27,
+        ],
+        names: &["romans", "rom", "ro", "rm"],
+    },
+    Book {
+        id: 46,
+        name: "1 Corinthians",
+        abbreviation: "1Co",
+        osis: "1Cor",
+        usfm: "1CO",
+        testament: Testament::New,
+        verses: &[
+            31, 16, 23, 21, 13, 20, 40, 13, 27, 33, 34, 31, 13, 40, 58, 24,
+        ],
+        names: &[
+            "1 corinthians",
+            "1 cor",
+            "1 co",
+            "i cor",
+            "i co",
+            "1cor",
+            "1co",
+            "i corinthians",
+            "1corinthians",
+            "1st corinthians",
+            "first corinthians",
+        ],
+    },
+    Book {
+        id: 47,
+        name: "2 Corinthians",
+        abbreviation: "2Co",
+        osis: "2Cor",
+        usfm: "2CO",
+        testament: Testament::New,
+        verses: &[24, 17, 18, 18, 21, 18, 16, 24, 15, 18, 33, 21, 14],
+        names: &[
+            "2 corinthians",
+            "2 cor",
+            "2 co",
+            "ii cor",
+            "ii co",
+            "2cor",
+            "2co",
+            "ii corinthians",
+            "2corinthians",
+            "2nd corinthians",
+            "second corinthians",
+        ],
+    },
+    Book {
+        id: 48,
+        name: "Galatians",
+        abbreviation: "Gal",
+        osis: "Gal",
+        usfm: "GAL",
+        testament: Testament::New,
+        verses: &[24, 21, 29, 31, 26, 18],
+        names: &["galatians", "gal", "ga"],
+    },
+    Book {
+        id: 49,
+        name: "Ephesians",
+        abbreviation: "Eph",
+        osis: "Eph",
+        usfm: "EPH",
+        testament: Testament::New,
+        verses: &[23, 22, 21, 32, 33, 24],
+        names: &["ephesians", "eph", "ephes"],
+    },
+    Book {
+        id: 50,
+        name: "Philippians",
+        abbreviation: "Php",
+        osis: "Phil",
+        usfm: "PHP",
+        testament: Testament::New,
+        verses: &[30, 30, 21, 23],
+        names: &["philippians", "phil", "php", "pp"],
+    },
+    Book {
+        id: 51,
+        name: "Colossians",
+        abbreviation: "Col",
+        osis: "Col",
+        usfm: "COL",
+        testament: Testament::New,
+        verses: &[29, 23, 25, 18],
+        names: &["colossians", "col"],
+    },
+    Book {
+        id: 52,
+        name: "1 Thessalonians",
+        abbreviation: "1Th",
+        osis: "1Thess",
+        usfm: "1TH",
+        testament: Testament::New,
+        verses: &[10, 20, 13, 18, 28],
+        names: &[
+            "1 thessalonians",
+            "1 thess",
+            "1 thes",
+            "1 th",
+            "i thess",
+            "i thes",
+            "i th",
+            "1thessalonians",
+            "1thess",
+            "1thes",
+            "1th",
+            "1st thessalonians",
+            "first thessalonians",
+        ],
+    },
+    Book {
+        id: 53,
+        name: "2 Thessalonians",
+        abbreviation: "2Th",
+        osis: "2Thess",
+        usfm: "2TH",
+        testament: Testament::New,
+        verses: &[12, 17, 18],
+        names: &[
+            "2 thessalonians",
+            "2 thess",
+            "2 thes",
+            "2 th",
+            "ii thess",
+            "ii thes",
+            "ii th",
+            "2thessalonians",
+            "2thess",
+            "2thes",
+            "2th",
+            "2nd thessalonians",
+            "second thessalonians",
+        ],
+    },
+    Book {
+        id: 54,
+        name: "1 Timothy",
+        abbreviation: "1Tm",
+        osis: "1Tim",
+        usfm: "1TI",
+        testament: Testament::New,
+        verses: &[20, 15, 16, 16, 25, 21],
+        names: &[
+            "1 timothy",
+            "1 tim",
+            "1 ti",
+            "i tim",
+            "i ti",
+            "1timothy",
+            "1tim",
+            "1ti",
+            "1st timothy",
+            "first timothy",
+        ],
+    },
+    Book {
+        id: 55,
+        name: "2 Timothy",
+        abbreviation: "2Tm",
+        osis: "2Tim",
+        usfm: "2TI",
+        testament: Testament::New,
+        verses: &[18, 26, 17, 22],
+        names: &[
+            "2 timothy",
+            "2 tim",
+            "2 ti",
+            "ii tim",
+            "ii ti",
+            "2timothy",
+            "2tim",
+            "2ti",
+            "2nd timothy",
+            "second timothy",
+        ],
+    },
+    Book {
+        id: 56,
+        name: "Titus",
+        abbreviation: "Ti",
+        osis: "Titus",
+        usfm: "TIT",
+        testament: Testament::New,
+        verses: &[16, 15, 15],
+        names: &["titus", "tit", "ti"],
+    },
+    Book {
+        id: 57,
+        name: "Philemon",
+        abbreviation: "Phm",
+        osis: "Phlm",
+        usfm: "PHM",
+        testament: Testament::New,
+        verses: &[25],
+        names: &["philemon", "philem", "phm", "pm"],
+    },
+    Book {
+        id: 58,
+        name: "Hebrews",
+        abbreviation: "Heb",
+        osis: "Heb",
+        usfm: "HEB",
+        testament: Testament::New,
+        verses: &[14, 18, 19, 16, 14, 20, 28, 13, 28, 39, 40, 29, 25],
+        names: &["hebrews", "heb"],
+    },
+    Book {
+        id: 59,
+        name: "James",
+        abbreviation: "Jas",
+        osis: "Jas",
+        usfm: "JAS",
+        testament: Testament::New,
+        verses: &[27, 26, 18, 17, 20],
+        names: &["james", "jas", "jm"],
+    },
+    Book {
+        id: 60,
+        name: "1 Peter",
+        abbreviation: "1Pt",
+        osis: "1Pet",
+        usfm: "1PE",
+        testament: Testament::New,
+        verses: &[25, 25, 22, 19, 14],
+        names: &[
+            "1 peter",
+            "1 pet",
+            "1 pe",
+            "1 pt",
+            "1 p",
+            "i pet",
+            "i pe",
+            "i pt",
+            "1peter",
+            "1pet",
+            "1pe",
+            "1pt",
+            "1p",
+            "1st peter",
+            "first peter",
+        ],
+    },
+    Book {
+        id: 61,
+        name: "2 Peter",
+        abbreviation: "2Pt",
+        osis: "2Pet",
+        usfm: "2PE",
+        testament: Testament::New,
+        verses: &[21, 22, 18],
+        names: &[
+            "2 peter",
+            "2 pet",
+            "2 pe",
+            "2 pt",
+            "2 p",
+            "ii pet",
+            "ii pe",
+            "ii pt",
+            "2peter",
+            "2pet",
+            "2pe",
+            "2pt",
+            "2p",
+            "2nd peter",
+            "second peter",
+        ],
+    },
+    Book {
+        id: 62,
+        name: "1 John",
+        abbreviation: "1Jn",
+        osis: "1John",
+        usfm: "1JN",
+        testament: Testament::New,
+        verses: &[10, 29, 24, 21, 21],
+        names: &[
+            "1 john",
+            "1 jhn",
+            "1 jn",
+            "1 j",
+            "1john",
+            "1jhn",
+            "1joh",
+            "1jn",
+            "1jo",
+            "1j",
+            "i john",
+            "i jhn",
+            "i joh",
+            "i jn",
+            "i jo",
+            "1st john",
+            "first john",
+        ],
+    },
+    Book {
+        id: 63,
+        name: "2 John",
+        abbreviation: "2Jn",
+        osis: "2John",
+        usfm: "2JN",
+        testament: Testament::New,
+        verses: &[13],
+        names: &[
+            "2 john",
+            "2 jhn",
+            "2 jn",
+            "2 j",
+            "2john",
+            "2jhn",
+            "2joh",
+            "2jn",
+            "2jo",
+            "2j",
+            "ii john",
+            "ii jhn",
+            "ii joh",
+            "ii jn",
+            "ii jo",
+            "2nd john",
+            "second john",
+        ],
+    },
+    Book {
+        id: 64,
+        name: "3 John",
+        abbreviation: "3Jn",
+        osis: "3John",
+        usfm: "3JN",
+        testament: Testament::New,
+        verses: &[14],
+        names: &[
+            "3 john",
+            "3 jhn",
+            "3 jn",
+            "3 j",
+            "3john",
+            "3jhn",
+            "3joh",
+            "3jn",
+            "3jo",
+            "3j",
+            "iii john",
+            "iii jhn",
+            "iii joh",
+            "iii jn",
+            "iii jo",
+            "3rd john",
+            "third john",
+        ],
+    },
+    Book {
+        id: 65,
+        name: "Jude",
+        abbreviation: "Jd",
+        osis: "Jude",
+        usfm: "JUD",
+        testament: Testament::New,
+        verses: &[25],
+        names: &["jude", "jud", "jd"],
+    },
+    Book {
+        id: 66,
+        name: "Revelation",
+        abbreviation: "Rev",
+        osis: "Rev",
+        usfm: "REV",
+        testament: Testament::New,
+        verses: &[
+            20, 29, 22, 11, 14, 17, 17, 13, 21, 11, 19, 17, 18, 20, 8, 21, 18, 24, 21, 15, 27, 21,
+        ],
+        names: &["revelation", "rev", "re"],
+    },
+    Book {
+        id: 67,
+        name: "Tobit",
+        abbreviation: "Tb",
+        osis: "Tob",
+        usfm: "TOB",
+        testament: Testament::Old,
+        verses: &[22, 14, 17, 21, 22, 17, 18, 21, 6, 12, 19, 22, 18, 15],
+        names: &["tobit", "tob", "tb", "tobias"],
+    },
+    Book {
+        id: 68,
+        name: "Judith",
+        abbreviation: "Jdt",
+        osis: "Jdt",
+        usfm: "JDT",
+        testament: Testament::Old,
+        verses: &[
+            16, 28, 10, 15, 24, 21, 32, 36, 14, 23, 23, 20, 20, 19, 13, 25,
+        ],
+        names: &["judith", "jdt", "jdth"],
+    },
+    Book {
+        id: 69,
+        name: "Additions to Esther",
+        abbreviation: "AEs",
+        osis: "AddEsth",
+        usfm: "ESG",
+        testament: Testament::Old,
+        // The additions start at 10:4, after the end of the Hebrew text.
+        verses: &[0, 0, 0, 0, 0, 0, 0, 0, 0, 13, 12, 6, 18, 19, 16, 24],
+        names: &[
+            "additions to esther",
+            "rest of esther",
+            "the rest of esther",
+            "add esth",
+            "addesth",
+            "aes",
+        ],
+    },
+    Book {
+        id: 70,
+        name: "Wisdom of Solomon",
+        abbreviation: "Ws",
+        osis: "Wis",
+        usfm: "WIS",
+        testament: Testament::Old,
+        verses: &[
+            16, 24, 19, 20, 23, 25, 30, 21, 18, 21, 26, 27, 19, 31, 19, 29, 21, 25, 22,
+        ],
+        names: &["wisdom of solomon", "wisdom", "wis", "wisd", "ws"],
+    },
+    Book {
+        id: 71,
+        name: "Sirach",
+        abbreviation: "Sir",
+        osis: "Sir",
+        usfm: "SIR",
+        testament: Testament::Old,
+        verses: &[
+            30, 18, 31, 31, 15, 37, 36, 19, 18, 31, 34, 18, 26, 27, 20, 30, 32, 33, 30, 32, 28, 27,
+            28, 34, 26, 29, 30, 26, 28, 25, 31, 24, 31, 26, 20, 26, 31, 34, 35, 30, 24, 25, 33, 23,
+            26, 20, 25, 25, 16, 29, 30,
+        ],
+        names: &["sirach", "ecclesiasticus", "ecclus", "ben sira"],
+    },
+    Book {
+        id: 72,
+        name: "Baruch",
+        abbreviation: "Bar",
+        osis: "Bar",
+        usfm: "BAR",
+        testament: Testament::Old,
+        verses: &[22, 35, 37, 37, 9, 73],
+        names: &["baruch"],
+    },
+    Book {
+        id: 73,
+        name: "Prayer of Azariah",
+        abbreviation: "Aza",
+        osis: "PrAzar",
+        usfm: "S3Y",
+        testament: Testament::Old,
+        verses: &[68],
+        names: &[
+            "prayer of azariah",
+            "azariah",
+            "song of the three holy children",
+            "song of the three young men",
+            "song of three",
+            "pr azar",
+        ],
+    },
+    Book {
+        id: 74,
+        name: "Susanna",
+        abbreviation: "Sus",
+        osis: "Sus",
+        usfm: "SUS",
+        testament: Testament::Old,
+        verses: &[64],
+        names: &["susanna", "susannah"],
+    },
+    Book {
+        id: 75,
+        name: "Bel and the Dragon",
+        abbreviation: "Bel",
+        osis: "Bel",
+        usfm: "BEL",
+        testament: Testament::Old,
+        verses: &[42],
+        names: &["bel and the dragon", "bel and dragon", "bel"],
+    },
+    Book {
+        id: 76,
+        name: "1 Maccabees",
+        abbreviation: "1Mc",
+        osis: "1Macc",
+        usfm: "1MA",
+        testament: Testament::Old,
+        verses: &[
+            64, 70, 60, 61, 68, 63, 50, 32, 73, 89, 74, 53, 53, 49, 41, 24,
+        ],
+        names: &[
+            "1 maccabees",
+            "1 macc",
+            "1 mac",
+            "1 mc",
+            "i maccabees",
+            "i macc",
+            "1macc",
+            "1mac",
+            "1mc",
+            "1st maccabees",
+            "1st macc",
+            "first maccabees",
+            "first macc",
+        ],
+    },
+    Book {
+        id: 77,
+        name: "2 Maccabees",
+        abbreviation: "2Mc",
+        osis: "2Macc",
+        usfm: "2MA",
+        testament: Testament::Old,
+        verses: &[36, 32, 40, 50, 27, 31, 42, 36, 29, 38, 38, 45, 26, 46, 39],
+        names: &[
+            "2 maccabees",
+            "2 macc",
+            "2 mac",
+            "2 mc",
+            "ii maccabees",
+            "ii macc",
+            "2macc",
+            "2mac",
+            "2mc",
+            "2nd maccabees",
+            "2nd macc",
+            "second maccabees",
+            "second macc",
+        ],
+    },
+];
+
+// Spanish names and abbreviations, as in the Reina-Valera, in canonical order.
+const SPANISH_NAMES: [&[&str]; 77] = [
+    &["génesis", "gén", "gn"],
+    &["éxodo", "éx"],
+    &["levítico", "lv"],
+    &["números", "nm"],
+    &["deuteronomio", "dt"],
+    &["josué", "jos"],
+    &["jueces", "jue"],
+    &["rut", "rt"],
+    &["1 samuel", "1 s"],
+    &["2 samuel", "2 s"],
+    &["1 reyes", "1 re", "1 r"],
+    &["2 reyes", "2 re", "2 r"],
+    &["1 crónicas", "1 cr"],
+    &["2 crónicas", "2 cr"],
+    &["esdras", "esd"],
+    &["nehemías", "neh"],
+    &["ester", "est"],
+    &["job"],
+    &["salmos", "salmo", "sal"],
+    &["proverbios", "prov", "pr"],
+    &["eclesiastés", "ecl", "ec"],
+    &["cantares", "cantar", "cnt"],
+    &["isaías", "is"],
+    &["jeremías", "jer"],
+    &["lamentaciones", "lm"],
+    &["ezequiel"],
+    &["daniel", "dn"],
+    &["oseas"],
+    &["joel", "jl"],
+    &["amós", "am"],
+    &["abdías", "abd"],
+    &["jonás", "jon"],
+    &["miqueas", "miq"],
+    &["nahúm", "nah"],
+    &["habacuc", "hab"],
+    &["sofonías", "sof"],
+    &["hageo", "hag"],
+    &["zacarías", "zac"],
+    &["malaquías", "mal"],
+    &["mateo", "mt"],
+    &["marcos", "mr"],
+    &["lucas", "lc"],
+    &["juan", "jn"],
+    &["hechos", "hch"],
+    &["romanos", "ro"],
+    &["1 corintios", "1 co"],
+    &["2 corintios", "2 co"],
+    &["gálatas", "gá"],
+    &["efesios", "ef"],
+    &["filipenses", "fil", "flp"],
+    &["colosenses", "col"],
+    &["1 tesalonicenses", "1 ts"],
+    &["2 tesalonicenses", "2 ts"],
+    &["1 timoteo", "1 ti"],
+    &["2 timoteo", "2 ti"],
+    &["tito", "tit"],
+    &["filemón", "flm"],
+    &["hebreos", "heb"],
+    &["santiago", "stg"],
+    &["1 pedro", "1 p"],
+    &["2 pedro", "2 p"],
+    &["1 juan", "1 jn"],
+    &["2 juan", "2 jn"],
+    &["3 juan", "3 jn"],
+    &["judas", "jud"],
+    &["apocalipsis", "apoc", "ap"],
+    &["tobías", "tob"],
+    &["judit", "jdt"],
+    &["adiciones a ester"],
+    &["sabiduría", "sab"],
+    &["eclesiástico", "sirácida", "eclo"],
+    &["baruc"],
+    &["oración de azarías"],
+    &["susana"],
+    &["bel y el dragón"],
+    &["1 macabeos", "1 mac"],
+    &["2 macabeos", "2 mac"],
+];
+
+// German names and abbreviations, as in the Luther Bible and the Loccum abbreviations, in
+// canonical order.
+const GERMAN_NAMES: [&[&str]; 77] = [
+    &["1 mose", "1 mo", "gen"],
+    &["2 mose", "2 mo", "ex"],
+    &["3 mose", "3 mo", "lev"],
+    &["4 mose", "4 mo", "num"],
+    &["5 mose", "5 mo", "dtn"],
+    &["josua", "jos"],
+    &["richter", "ri"],
+    &["rut"],
+    &["1 samuel", "1 sam"],
+    &["2 samuel", "2 sam"],
+    &["1 könige", "1 kön"],
+    &["2 könige", "2 kön"],
+    &["1 chronik", "1 chr"],
+    &["2 chronik", "2 chr"],
+    &["esra", "esr"],
+    &["nehemia", "neh"],
+    &["ester", "est"],
+    &["hiob", "ijob"],
+    &["psalmen", "psalm", "ps"],
+    &["sprüche", "sprichwörter", "spr"],
+    &["prediger", "kohelet", "pred", "koh"],
+    &["hoheslied", "hld"],
+    &["jesaja", "jes"],
+    &["jeremia", "jer"],
+    &["klagelieder", "klgl"],
+    &["hesekiel", "ezechiel", "hes"],
+    &["daniel", "dan"],
+    &["hosea", "hos"],
+    &["joel"],
+    &["amos"],
+    &["obadja", "obd"],
+    &["jona"],
+    &["micha", "mi"],
+    &["nahum", "nah"],
+    &["habakuk", "hab"],
+    &["zefanja", "zephanja", "zef"],
+    &["haggai", "hag"],
+    &["sacharja", "sach"],
+    &["maleachi", "mal"],
+    &["matthäus", "mt"],
+    &["markus", "mk"],
+    &["lukas", "lk"],
+    &["johannes", "joh"],
+    &["apostelgeschichte", "apg"],
+    &["römer", "roemer", "röm"],
+    &["1 korinther", "1 kor"],
+    &["2 korinther", "2 kor"],
+    &["galater", "gal"],
+    &["epheser", "eph"],
+    &["philipper", "phil"],
+    &["kolosser", "kol"],
+    &["1 thessalonicher", "1 thess"],
+    &["2 thessalonicher", "2 thess"],
+    &["1 timotheus", "1 tim"],
+    &["2 timotheus", "2 tim"],
+    &["titus", "tit"],
+    &["philemon", "phlm"],
+    &["hebräer", "hebr"],
+    &["jakobus", "jak"],
+    &["1 petrus", "1 petr"],
+    &["2 petrus", "2 petr"],
+    &["1 johannes", "1 joh"],
+    &["2 johannes", "2 joh"],
+    &["3 johannes", "3 joh"],
+    &["judas", "jud"],
+    &["offenbarung", "offb"],
+    &["tobit", "tob"],
+    &["judit", "jdt"],
+    &["zusätze zu ester", "stest"],
+    &["weisheit", "weish"],
+    &["jesus sirach", "sirach"],
+    &["baruch"],
+    &["gebet asarjas", "gebasar"],
+    &["susanna"],
+    &["bel und der drache", "bel"],
+    &["1 makkabäer", "1 makk"],
+    &["2 makkabäer", "2 makk"],
+];
+
+impl Book {
+    /// The book with an id, e.g. 43 is John.
+    pub fn get(id: u8) -> Option<&'static Book> {
+        BOOKS.get(usize::from(id).checked_sub(1)?)
+    }
+
+    /// The book for any recognised name or abbreviation, e.g. "1 jn", "Romans" or "Römer".
+    pub fn find(name: &str) -> Option<&'static Book> {
+        Book::find_with_language(name).map(|(book, _)| book)
+    }
+
+    /// The book for a name and the language the name is from.  Names English shares with
+    /// another language, such as Daniel, are English.
+    pub fn find_with_language(name: &str) -> Option<(&'static Book, Language)> {
+        let (id, language) = names().get(&fold(name))?;
+        Some((Book::get(*id)?, *language))
+    }
+
+    /// The book a misspelt name is close enough to be read as, e.g. "Phillipians" or
+    /// "Revelations".  Only names of five or more letters are corrected, allowing one typo, two
+    /// from eight letters and three from twelve, and a name equally close to two books is left
+    /// alone.
+    pub fn find_fuzzy(name: &str) -> Option<&'static Book> {
+        Book::find(name).or_else(|| closest(name, 0))
+    }
+
+    /// A book to suggest for a name that is not close enough for `find_fuzzy`, allowing one more
+    /// typo, e.g. "Mathews" suggests Matthew.
+    pub fn suggest(name: &str) -> Option<&'static Book> {
+        Book::find(name).or_else(|| closest(name, 1))
+    }
+
+    pub fn chapters(&self) -> u8 {
+        self.verses.len() as u8
+    }
+
+    /// The number of verses in a chapter, or None past the last chapter.
+    pub fn verses_in(&self, chapter: u8) -> Option<u8> {
+        self.verses
+            .get(usize::from(chapter).checked_sub(1)?)
+            .copied()
+    }
+
+    /// The number of verses in the whole book.
+    pub fn verse_count(&self) -> u16 {
+        self.verses.iter().map(|verses| u16::from(*verses)).sum()
+    }
+
+    /// Whether the book is only in Catholic and Orthodox Bibles, e.g. Tobit.
+    pub fn is_deuterocanonical(&self) -> bool {
+        !Canon::Protestant.contains(self.id)
+    }
+
+    /// Checks that a chapter and verse are in the book, with the same errors a translation gives.
+    pub fn check_verse(&self, chapter: u8, verse: u8) -> Result<(), ReferenceError> {
+        let verses = self
+            .verses_in(chapter)
+            .ok_or_else(|| ReferenceError::ChapterOutOfRange {
+                book: self.name.to_string(),
+                chapter,
+                chapters: self.chapters(),
+            })?;
+        if verse == 0 || verse > verses {
+            return Err(ReferenceError::VerseOutOfRange {
+                book: self.name.to_string(),
+                chapter,
+                verse,
+                verses,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Canon {
+    pub const ALL: [Canon; 2] = [Canon::Protestant, Canon::Catholic];
+
+    pub fn contains(self, book: u8) -> bool {
+        match self {
+            Canon::Protestant => (1..=66).contains(&book),
+            Canon::Catholic => Book::get(book).is_some(),
+        }
+    }
+
+    /// The books of the canon in order.
+    pub fn books(self) -> impl Iterator<Item = &'static Book> {
+        BOOKS.iter().filter(move |book| self.contains(book.id))
+    }
+}
+
+fn names() -> &'static HashMap<String, (u8, Language)> {
+    static NAMES: OnceLock<HashMap<String, (u8, Language)>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        let mut names = HashMap::new();
+        for book in &BOOKS {
+            for name in book.names {
+                names.insert(fold(name), (book.id, Language::English));
+            }
+        }
+        for (language, table) in [
+            (Language::Spanish, &SPANISH_NAMES),
+            (Language::German, &GERMAN_NAMES),
+        ] {
+            for (book, localized) in BOOKS.iter().zip(table) {
+                for name in localized
+                    .iter()
+                    .flat_map(|name| [fold(name), fold(name).replacen(' ', "", 1)])
+                {
+                    // English names keep their meaning, as does a name two languages share.
+                    names.entry(name).or_insert((book.id, language));
+                }
+            }
+        }
+        names
+    })
+}
+
+/// A regex alternation of every recognised name, longest first so "song of solomon" is tried
+/// before "sos" and "1 john" before "john".  Names match as written, with or without accents
+/// and full stops, e.g. "Römer" or "1. Mose".
+pub(crate) fn name_pattern() -> String {
+    let mut names: Vec<&String> = names().keys().collect();
+    names.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    let names: Vec<String> = names
+        .into_iter()
+        .map(|name| {
+            name.chars()
+                .map(|c| match c {
+                    ' ' => r"\.?\s?".to_string(),
+                    'a' => "[aáàâä]".to_string(),
+                    'e' => "[eéèê]".to_string(),
+                    'i' => "[iíìî]".to_string(),
+                    'o' => "[oóòôö]".to_string(),
+                    'u' => "[uúùûü]".to_string(),
+                    'n' => "[nñ]".to_string(),
+                    c => regex::escape(&c.to_string()),
+                })
+                .collect()
+        })
+        .collect();
+    names.join("|")
+}
+
+// The book with the name nearest to a misspelling, allowing `extra` typos more than
+// `find_fuzzy`.  A leading book number must match, so 1 John is never read as 2 John, and so
+// must the first letter, so ordinary words such as "games" are not read as James.
+fn closest(name: &str, extra: usize) -> Option<&'static Book> {
+    let name = fold(name);
+    let letters = name.chars().filter(|c| c.is_alphabetic()).count();
+    let allowed = match letters {
+        0..=4 => return None,
+        5..=7 => 1,
+        8..=11 => 2,
+        _ => 3,
+    } + extra;
+    let number = |name: &str| {
+        name.chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+    };
+    let first_letter = |name: &str| name.chars().find(|c| c.is_alphabetic());
+
+    let mut best: Option<(usize, u8)> = None;
+    let mut tied = false;
+    for (candidate, (id, _)) in names() {
+        if candidate.chars().filter(|c| c.is_alphabetic()).count() < 4
+            || number(candidate) != number(&name)
+            || first_letter(candidate) != first_letter(&name)
+        {
+            continue;
+        }
+        let distance = edit_distance(&name, candidate);
+        match best {
+            _ if distance > allowed => (),
+            Some((best_distance, best_id)) if distance == best_distance => {
+                tied |= *id != best_id;
+            }
+            Some((best_distance, _)) if distance > best_distance => (),
+            _ => {
+                best = Some((distance, *id));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|_| !tied).and_then(|(_, id)| Book::get(id))
+}
+
+// The number of single letter insertions, deletions, substitutions and swaps of neighbouring
+// letters that turn one word into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+// Lowercases a book name and drops accents, full stops and extra spaces, so "1. Mose" is
+// "1 mose" and "Römer" is "romer".
+fn fold(name: &str) -> String {
+    let folded: String = name
+        .to_lowercase()
+        .chars()
+        .filter(|c| *c != '.')
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' => 'e',
+            'í' | 'ì' | 'î' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            c => c,
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl fmt::Display for Testament {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Testament::Old => write!(f, "Old Testament"),
+            Testament::New => write!(f, "New Testament"),
+        }
+    }
+}
+
+impl fmt::Display for Canon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Canon::Protestant => write!(f, "Protestant"),
+            Canon::Catholic => write!(f, "Catholic"),
+        }
+    }
+}
+
+impl FromStr for Canon {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Canon::ALL
+            .into_iter()
+            .find(|canon| canon.to_string().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("{} is not a known canon", name))
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn registry_is_complete() {
+        assert!(BOOKS
+            .iter()
+            .enumerate()
+            .all(|(i, book)| usize::from(book.id) == i + 1));
+        assert_eq!(
+            Canon::Protestant
+                .books()
+                .map(|book| usize::from(book.chapters()))
+                .sum::<usize>(),
+            1189
+        );
+        assert_eq!(
+            Canon::Protestant
+                .books()
+                .map(|book| usize::from(book.verse_count()))
+                .sum::<usize>(),
+            31102
+        );
+        assert_eq!(
+            BOOKS
+                .iter()
+                .filter(|book| book.testament == Testament::New)
+                .count(),
+            27
+        );
+    }
+
+    #[test]
+    fn deuterocanonical_books() {
+        assert_eq!(Canon::Catholic.books().count(), 77);
+        assert_eq!(
+            BOOKS
+                .iter()
+                .filter(|book| book.is_deuterocanonical())
+                .map(|book| book.osis)
+                .collect::<Vec<_>>(),
+            [
+                "Tob", "Jdt", "AddEsth", "Wis", "Sir", "Bar", "PrAzar", "Sus", "Bel", "1Macc",
+                "2Macc"
+            ]
+        );
+        for (name, id) in [
+            ("Ecclesiasticus", 71),
+            ("2 Macc", 77),
+            ("First Maccabees", 76),
+            ("Weisheit", 70),
+            ("Tobías", 67),
+        ] {
+            assert_eq!(Book::find(name).map(|book| book.id), Some(id));
+        }
+        let esther = Book::get(69).unwrap();
+        assert!(esther.check_verse(16, 24).is_ok());
+        assert!(esther.check_verse(3, 1).is_err());
+        assert_eq!("catholic".parse(), Ok(Canon::Catholic));
+        assert!(!Canon::Protestant.contains(72));
+    }
+
+    #[test]
+    fn find_and_check_books() {
+        let romans = Book::find("Romans").unwrap();
+        assert_eq!((romans.id, romans.osis, romans.chapters()), (45, "Rom", 16));
+        assert_eq!(romans.verse_count(), 433);
+        assert_eq!(Book::find(" 1 JN "), Book::get(62));
+        assert_eq!(
+            Book::find("psalms").and_then(|book| book.verses_in(119)),
+            Some(176)
+        );
+        assert_eq!(Book::find("hezekiah"), None);
+        assert_eq!(
+            Book::find_with_language("1. Mose"),
+            Some((&BOOKS[0], Language::German))
+        );
+        assert_eq!(Book::find("Apocalipsis"), Book::get(66));
+    }
+
+    #[test]
+    fn fuzzy_book_names() {
+        for (typo, book) in [
+            ("Phillipians", 50),
+            ("Revelations", 66),
+            ("Eccelsiastes", 21),
+            ("Mathew", 40),
+            ("1 Corinthans", 46),
+        ] {
+            assert_eq!(Book::find_fuzzy(typo), Book::get(book), "{}", typo);
+        }
+        // Words of four letters or fewer are never corrected, nor are typos in the first letter.
+        assert_eq!(Book::find_fuzzy("june"), None);
+        assert_eq!(Book::find_fuzzy("games"), None);
+        assert_eq!(
+            Book::find_fuzzy("2 Corinthans").map(|book| book.id),
+            Some(47)
+        );
+        assert_eq!(Book::find_fuzzy("Mathews"), None);
+        assert_eq!(Book::suggest("Mathews"), Book::get(40));
+        // Zechaniah is as close to Zechariah as to Zephaniah.
+        assert_eq!(Book::suggest("Zechaniah"), None);
+        assert_eq!(
+            Book::find_with_language("daniel"),
+            Some((&BOOKS[26], Language::English))
+        );
+
+        let john = Book::get(43).unwrap();
+        assert_eq!(john.check_verse(3, 16), Ok(()));
+        assert_eq!(
+            john.check_verse(22, 1).unwrap_err().to_string(),
+            "John only has 21 chapters"
+        );
+        assert_eq!(
+            john.check_verse(3, 37).unwrap_err().to_string(),
+            "John 3 only has 36 verses"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::scripture::bible::{Bible, Verse};
+use crate::scripture::reference::{Reference, ReferenceError};
+use crate::scripture::versification::Versification;
+
+/// One translation's verses for a compared reference.
+#[derive(Debug)]
+pub struct Comparison<'a, T> {
+    pub translation: T,
+    pub verses: Result<&'a [Verse], ReferenceError>,
+}
+
+/// Looks up the same reference in several translations to show them side by side.  The
+/// reference is read in KJV numbering and mapped to each translation's own versification, so
+/// Malachi 4:5 finds Malachi 3:23 in a Hebrew ordered translation.
+pub fn compare<'a, T>(
+    reference: &Reference,
+    bibles: impl IntoIterator<Item = (T, &'a Bible)>,
+) -> Vec<Comparison<'a, T>> {
+    bibles
+        .into_iter()
+        .map(|(translation, bible)| {
+            let reference = Versification::Kjv.convert_reference(reference, bible.versification());
+            Comparison {
+                translation,
+                verses: bible.get_verses(&reference),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    fn bible(chapters: &[(u8, u8)]) -> Bible {
+        let mut bible = Bible::new();
+        for &(chapter, verses) in chapters {
+            for verse in 1..=verses {
+                let text = format!("Malachi {}:{}", chapter, verse);
+                bible.insert(Verse::new(39, chapter, verse, text));
+            }
+        }
+        bible
+    }
+
+    #[test]
+    fn compares_across_versifications() {
+        let kjv = bible(&[(3, 18), (4, 6)]);
+        let hebrew = bible(&[(3, 24)]);
+        let short = bible(&[(3, 18), (4, 4)]);
+
+        let reference = "Malachi 4:5-6".parse().unwrap();
+        let comparisons = compare(
+            &reference,
+            [("KJV", &kjv), ("WLC", &hebrew), ("SHORT", &short)],
+        );
+
+        let texts: Vec<_> = comparisons
+            .iter()
+            .map(|comparison| {
+                let verses = comparison.verses.as_ref().ok()?;
+                Some(
+                    verses
+                        .iter()
+                        .map(|verse| verse.scripture.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(texts[0], Some(vec!["Malachi 4:5", "Malachi 4:6"]));
+        assert_eq!(texts[1], Some(vec!["Malachi 3:23", "Malachi 3:24"]));
+        assert_eq!(comparisons[2].translation, "SHORT");
+        assert!(matches!(
+            comparisons[2].verses,
+            Err(ReferenceError::VerseOutOfRange { .. })
+        ));
+    }
+}
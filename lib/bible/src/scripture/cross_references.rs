@@ -0,0 +1,210 @@
+use crate::scripture::bible::VerseId;
+use crate::scripture::books::Book;
+use crate::scripture::reference::{osis_book_id, Location, Reference};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+
+/// A passage related to a verse, with the votes readers gave the link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossReference {
+    pub reference: Reference,
+    pub votes: i32,
+}
+
+/// Related passages for each verse, in KJV numbering, such as the OpenBible.info cross references
+/// or the Treasury of Scripture Knowledge.
+#[derive(Debug, Default)]
+pub struct CrossReferences {
+    // Most votes first.
+    references: HashMap<VerseId, Vec<CrossReference>>,
+}
+
+impl CrossReferences {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn import(cross_references_path: &str) -> Result<Self, Box<dyn Error>> {
+        Self::from_reader(File::open(cross_references_path)?)
+    }
+
+    /// Reads tab separated lines of from verse, to passage and optionally votes, as in the
+    /// OpenBible.info file where John.3.16, Rom.5.8 and 735 is a line and ranges are written as
+    /// 1John.4.9-1John.4.10.  References may also be written out as in John 3:16.  Lines without
+    /// a verse to link from, such as a header, are skipped, as are links readers voted down.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Box<dyn Error>> {
+        let mut tsv_reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .has_headers(false)
+            .flexible(true)
+            .comment(Some(b'#'))
+            .from_reader(reader);
+        let mut cross_references = Self::new();
+
+        for result in tsv_reader.records() {
+            let record = result?;
+            let from = record.get(0).and_then(reference).and_then(|from| {
+                let verse = from.start.verse?;
+                Some(VerseId::new(from.start.book, from.start.chapter, verse))
+            });
+            let (Some(from), Some(to)) = (from, record.get(1)) else {
+                continue;
+            };
+            let to = reference(to)
+                .ok_or_else(|| format!("\"{}\" is not a scripture reference", to.trim()))?;
+            let votes = match record.get(2).map(str::trim) {
+                Some(votes) if !votes.is_empty() => votes.parse()?,
+                _ => 0,
+            };
+            if votes >= 0 {
+                cross_references.insert(
+                    from,
+                    CrossReference {
+                        reference: to,
+                        votes,
+                    },
+                );
+            }
+        }
+        for related in cross_references.references.values_mut() {
+            related.sort_by_key(|cross_reference| -cross_reference.votes);
+        }
+        Ok(cross_references)
+    }
+
+    pub fn insert(&mut self, from: VerseId, cross_reference: CrossReference) {
+        self.references
+            .entry(from)
+            .or_default()
+            .push(cross_reference);
+    }
+
+    /// The passages related to one verse, most votes first.
+    pub fn get(&self, id: VerseId) -> &[CrossReference] {
+        self.references.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The passages most related to a passage, adding up the votes of its verses and leaving
+    /// out links within the passage itself.
+    pub fn related(&self, reference: &Reference, limit: usize) -> Vec<CrossReference> {
+        let (start, end) = (reference.start, reference.end);
+        let last_verse = end
+            .verse
+            .or_else(|| Book::get(end.book)?.verses_in(end.chapter))
+            .unwrap_or(u8::MAX);
+        let first = VerseId::new(start.book, start.chapter, start.verse.unwrap_or(1));
+        let last = VerseId::new(end.book, end.chapter, last_verse);
+
+        let mut votes: HashMap<Reference, i32> = HashMap::new();
+        let mut order = Vec::new();
+        let mut ids: Vec<&VerseId> = self
+            .references
+            .keys()
+            .filter(|id| (first..=last).contains(*id))
+            .collect();
+        ids.sort();
+        for cross_reference in ids.into_iter().flat_map(|id| &self.references[id]) {
+            let to = cross_reference.reference.start;
+            let to = VerseId::new(to.book, to.chapter, to.verse.unwrap_or(1));
+            if (first..=last).contains(&to) {
+                continue;
+            }
+            let total = votes.entry(cross_reference.reference).or_insert_with(|| {
+                order.push(cross_reference.reference);
+                0
+            });
+            *total += cross_reference.votes;
+        }
+
+        // Stable, so passages with the same votes stay in the order the dataset gave them.
+        order.sort_by_key(|reference| -votes[reference]);
+        order
+            .into_iter()
+            .take(limit)
+            .map(|reference| CrossReference {
+                reference,
+                votes: votes[&reference],
+            })
+            .collect()
+    }
+
+    /// The number of verses with cross references.
+    pub fn len(&self) -> usize {
+        self.references.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty()
+    }
+}
+
+// An OSIS reference such as John.3.16, Ps.23 or Prov.8.22-Prov.8.30, or a written out one.
+fn reference(text: &str) -> Option<Reference> {
+    let text = text.trim();
+    let osis = match text.split_once('-') {
+        Some((start, end)) => osis_location(start).zip(osis_location(end)),
+        None => osis_location(text).map(|location| (location, location)),
+    };
+    match osis {
+        Some((start, end)) => Some(Reference::new(start, end)),
+        None => text.parse().ok(),
+    }
+}
+
+fn osis_location(text: &str) -> Option<Location> {
+    let mut parts = text.split('.');
+    let book = osis_book_id(parts.next()?)?;
+    let chapter = parts.next()?.parse().ok()?;
+    let verse = parts.next().map(str::parse).transpose().ok()?;
+    Some(Location::new(book, chapter, verse))
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    const DATASET: &str = "From Verse\tTo Verse\tVotes\t#www.openbible.info CC-BY 2024-01-01
+John.3.16\tRom.5.8\t735
+John.3.16\t1John.4.9-1John.4.10\t512
+John.3.16\tJohn.3.17\t90
+John.3.16\tNum.21.8\t-3
+John.3.17\tRom.5.8\t100
+John.3.17\tJohn 12:47
+";
+
+    #[test]
+    fn import_and_rank_cross_references() {
+        let cross_references = CrossReferences::from_reader(DATASET.as_bytes()).unwrap();
+        assert_eq!(cross_references.len(), 2);
+
+        let related = cross_references.get(VerseId::new(43, 3, 16));
+        let texts: Vec<String> = related.iter().map(|r| r.reference.to_string()).collect();
+        assert_eq!(texts, ["Romans 5:8", "1 John 4:9-10", "John 3:17"]);
+
+        // Verses within the passage are left out and votes for the same passage add up.
+        let related = cross_references.related(&"John 3:16-17".parse().unwrap(), 5);
+        let related: Vec<(String, i32)> = related
+            .iter()
+            .map(|r| (r.reference.to_string(), r.votes))
+            .collect();
+        assert_eq!(
+            related,
+            [
+                ("Romans 5:8".to_string(), 835),
+                ("1 John 4:9-10".to_string(), 512),
+                ("John 12:47".to_string(), 0)
+            ]
+        );
+        assert_eq!(
+            cross_references.related(&"John 3".parse().unwrap(), 1)[0].votes,
+            835
+        );
+        assert!(cross_references
+            .related(&"Genesis 1:1".parse().unwrap(), 5)
+            .is_empty());
+
+        assert!(CrossReferences::from_reader("John.3.16\tNowhere 1:1\t5".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::scripture::bible::{Bible, Verse};
+use crate::scripture::compare::compare;
+use crate::scripture::reference::{Reference, ReferenceError};
+
+/// A word of one verse compared with the same verse in another translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordChange<'a> {
+    Same(&'a str),
+    OnlyLeft(&'a str),
+    OnlyRight(&'a str),
+}
+
+impl<'a> WordChange<'a> {
+    /// The word as written in its translation.
+    pub fn word(&self) -> &'a str {
+        match self {
+            WordChange::Same(word) | WordChange::OnlyLeft(word) | WordChange::OnlyRight(word) => {
+                word
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VerseDiff<'a> {
+    pub reference: &'a str,
+    pub changes: Vec<WordChange<'a>>,
+}
+
+/// The word differences between two translations of a passage, verse by verse.
+#[derive(Debug)]
+pub struct PassageDiff<'a> {
+    pub verses: Vec<VerseDiff<'a>>,
+}
+
+/// Compares a passage, read in KJV numbering, in two translations.  Words match regardless of
+/// case and punctuation, so "Word," and "word" are the same.
+pub fn diff<'a>(
+    reference: &Reference,
+    left: &'a Bible,
+    right: &'a Bible,
+) -> Result<PassageDiff<'a>, ReferenceError> {
+    let mut comparisons = compare(reference, [left, right].map(|bible| ((), bible))).into_iter();
+    let (Some(left), Some(right)) = (comparisons.next(), comparisons.next()) else {
+        return Ok(PassageDiff { verses: Vec::new() });
+    };
+    let (left, right) = (left.verses?, right.verses?);
+
+    // Verses are paired in order, a verse one translation splits in two is compared with nothing.
+    let verses = (0..left.len().max(right.len()))
+        .map(|index| verse_diff(left.get(index), right.get(index)))
+        .collect();
+    Ok(PassageDiff { verses })
+}
+
+/// The words of two texts with the longest run of shared words marked as the same.
+pub fn diff_words<'a>(left: &'a str, right: &'a str) -> Vec<WordChange<'a>> {
+    let (left, right) = (words(left), words(right));
+
+    // lengths[i][j] is the longest common subsequence of left[i..] and right[j..].
+    let mut lengths = vec![vec![0usize; right.len() + 1]; left.len() + 1];
+    for i in (0..left.len()).rev() {
+        for j in (0..right.len()).rev() {
+            lengths[i][j] = if left[i].1 == right[j].1 {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(left.len().max(right.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() || j < right.len() {
+        if i < left.len() && j < right.len() && left[i].1 == right[j].1 {
+            changes.push(WordChange::Same(left[i].0));
+            i += 1;
+            j += 1;
+        } else if j == right.len() || (i < left.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            changes.push(WordChange::OnlyLeft(left[i].0));
+            i += 1;
+        } else {
+            changes.push(WordChange::OnlyRight(right[j].0));
+            j += 1;
+        }
+    }
+    changes
+}
+
+fn verse_diff<'a>(left: Option<&'a Verse>, right: Option<&'a Verse>) -> VerseDiff<'a> {
+    let text = |verse: Option<&'a Verse>| verse.map_or("", |verse| verse.scripture.as_str());
+    VerseDiff {
+        reference: left.or(right).map_or("", |verse| verse.reference.as_str()),
+        changes: diff_words(text(left), text(right)),
+    }
+}
+
+// Each word as written and as compared, leaving out punctuation such as a lone dash.
+fn words(text: &str) -> Vec<(&str, String)> {
+    text.split_whitespace()
+        .map(|word| {
+            let normalized: String = word
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect();
+            (word, normalized)
+        })
+        .filter(|(_, normalized)| !normalized.is_empty())
+        .collect()
+}
+
+impl PassageDiff<'_> {
+    /// How many words are only in one of the translations.
+    pub fn changed_words(&self) -> usize {
+        self.changes()
+            .filter(|change| !matches!(change, WordChange::Same(_)))
+            .count()
+    }
+
+    /// The runs of words only in the left translation, e.g. ["only begotten", "whosoever"].
+    pub fn left_only(&self) -> Vec<String> {
+        self.runs(|change| matches!(change, WordChange::OnlyLeft(_)))
+    }
+
+    /// The runs of words only in the right translation.
+    pub fn right_only(&self) -> Vec<String> {
+        self.runs(|change| matches!(change, WordChange::OnlyRight(_)))
+    }
+
+    /// The diff as plain text, one verse per line with [-left-] and {+right+} words.
+    pub fn to_text(&self) -> String {
+        self.render(("[-", "-]"), ("{+", "+}"), |reference| {
+            format!("{}:", reference)
+        })
+    }
+
+    /// The diff as markdown, one paragraph per verse with left words struck through and right
+    /// words in bold.
+    pub fn to_markdown(&self) -> String {
+        self.render(("~~", "~~"), ("**", "**"), |reference| {
+            format!("**{}**", reference)
+        })
+        .replace('\n', "\n\n")
+    }
+
+    fn changes(&self) -> impl Iterator<Item = &WordChange<'_>> {
+        self.verses.iter().flat_map(|verse| &verse.changes)
+    }
+
+    fn runs(&self, included: impl Fn(&WordChange) -> bool) -> Vec<String> {
+        self.verses
+            .iter()
+            .flat_map(|verse| verse.changes.chunk_by(|a, b| included(a) == included(b)))
+            .filter(|run| included(&run[0]))
+            .map(|run| {
+                run.iter()
+                    .map(WordChange::word)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    fn render(
+        &self,
+        left: (&str, &str),
+        right: (&str, &str),
+        heading: impl Fn(&str) -> String,
+    ) -> String {
+        let mut lines = Vec::with_capacity(self.verses.len());
+        for verse in &self.verses {
+            let mut line = heading(verse.reference);
+            let same_kind = |a: &WordChange, b: &WordChange| {
+                std::mem::discriminant(a) == std::mem::discriminant(b)
+            };
+            for run in verse.changes.chunk_by(same_kind) {
+                let (open, close) = match run[0] {
+                    WordChange::Same(_) => ("", ""),
+                    WordChange::OnlyLeft(_) => left,
+                    WordChange::OnlyRight(_) => right,
+                };
+                let words: Vec<&str> = run.iter().map(WordChange::word).collect();
+                line.push_str(&format!(" {}{}{}", open, words.join(" "), close));
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn diff_words_ignores_case_and_punctuation() {
+        assert_eq!(
+            diff_words(
+                "For God so loved the world, that he gave his only begotten Son",
+                "For God so loved the world that he gave his one and only Son"
+            )
+            .iter()
+            .filter(|change| !matches!(change, WordChange::Same(_)))
+            .collect::<Vec<_>>(),
+            [
+                &WordChange::OnlyRight("one"),
+                &WordChange::OnlyRight("and"),
+                &WordChange::OnlyLeft("begotten"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_passage_and_export() {
+        let (mut kjv, mut web) = (Bible::new(), Bible::new());
+        kjv.insert(Verse::new(
+            43,
+            1,
+            1,
+            "In the beginning was the Word.".into(),
+        ));
+        kjv.insert(Verse::new(
+            43,
+            1,
+            2,
+            "The same was in the beginning with God.".into(),
+        ));
+        web.insert(Verse::new(
+            43,
+            1,
+            1,
+            "In the beginning was the Word.".into(),
+        ));
+        web.insert(Verse::new(
+            43,
+            1,
+            2,
+            "The same was with God in the beginning.".into(),
+        ));
+
+        let passage = diff(&"John 1:1-2".parse().unwrap(), &kjv, &web).unwrap();
+        assert_eq!(passage.changed_words(), 4);
+        assert_eq!(passage.left_only(), ["with God."]);
+        assert_eq!(passage.right_only(), ["with God"]);
+        assert_eq!(
+            passage.to_text(),
+            "John 1:1: In the beginning was the Word.\n\
+             John 1:2: The same was {+with God+} in the beginning [-with God.-]"
+        );
+        assert!(passage
+            .to_markdown()
+            .ends_with("**John 1:2** The same was **with God** in the beginning ~~with God.~~"));
+        assert!(diff(&"John 2:1".parse().unwrap(), &kjv, &web).is_err());
+    }
+}
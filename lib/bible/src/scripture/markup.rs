@@ -0,0 +1,324 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+/// What a part of a verse's text is, beyond its words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Markup {
+    /// Words of Jesus, printed in red in red-letter editions.
+    WordsOfJesus,
+    /// Words the translators supplied for the sense, printed in italics in the KJV.
+    Supplied,
+    /// A footnote at this point of the text, with the note.
+    Footnote(String),
+}
+
+/// Markup over part of a verse, as byte offsets into its text.  Footnotes are empty spans where
+/// the note is marked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub markup: Markup,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// How markup is shown when verse text is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextStyle {
+    /// The words alone.
+    #[default]
+    Plain,
+    /// [Supplied] words and ‹words of Jesus› marked with brackets.
+    Brackets,
+    /// Supplied words in italic and words of Jesus in bold Unicode letters.
+    Unicode,
+    /// Everything, footnotes included, in the notation verse text is imported from, so the text
+    /// can be read back with its markup.
+    Markup,
+}
+
+impl TextStyle {
+    pub const ALL: [TextStyle; 4] = [
+        TextStyle::Plain,
+        TextStyle::Brackets,
+        TextStyle::Unicode,
+        TextStyle::Markup,
+    ];
+}
+
+impl fmt::Display for TextStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TextStyle::Plain => "plain",
+            TextStyle::Brackets => "brackets",
+            TextStyle::Unicode => "unicode",
+            TextStyle::Markup => "markup",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for TextStyle {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_lowercase().as_str() {
+            "plain" | "strip" | "none" => Ok(TextStyle::Plain),
+            "brackets" | "bracket" => Ok(TextStyle::Brackets),
+            "unicode" | "styled" => Ok(TextStyle::Unicode),
+            "markup" | "full" => Ok(TextStyle::Markup),
+            _ => Err(format!("{} is not a text style", text.trim())),
+        }
+    }
+}
+
+// Characters with a meaning in marked up text, which are escaped with a backslash to be read as
+// themselves.
+const MARKS: [char; 7] = ['[', ']', '‹', '›', '{', '}', '\\'];
+
+/// Escapes the characters of text that `parse` would read as markup, for importers building
+/// marked up text from the words of a file.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKS.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Separates verse text written with markup into its words and the spans marked, where
+/// [brackets] are supplied words, ‹angle quotes› words of Jesus and {braces} footnotes, and a
+/// backslash escapes the character after it.  Quotes left open run to the end of the verse and
+/// quotes closed without opening run from its start, as the words of Jesus often go on over
+/// several verses.  Whitespace is collapsed.
+pub fn parse(text: &str) -> (String, Vec<Span>) {
+    let mut words = String::with_capacity(text.len());
+    let mut spans = Vec::new();
+    let mut open: Vec<(Markup, usize)> = Vec::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        let markup = match c {
+            '[' | ']' => Markup::Supplied,
+            '‹' | '›' => Markup::WordsOfJesus,
+            '\\' => {
+                words.extend(chars.next());
+                continue;
+            }
+            '{' => {
+                let mut note = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '}' => break,
+                        '\\' => note.extend(chars.next()),
+                        _ => note.push(c),
+                    }
+                }
+                let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+                let at = words.trim_end().len();
+                spans.push(Span {
+                    markup: Markup::Footnote(note),
+                    start: at,
+                    end: at,
+                });
+                continue;
+            }
+            _ if c.is_whitespace() => {
+                if !words.is_empty() && !words.ends_with(' ') {
+                    words.push(' ');
+                }
+                continue;
+            }
+            _ => {
+                words.push(c);
+                continue;
+            }
+        };
+        if matches!(c, '[' | '‹') {
+            open.push((markup, words.len()));
+            continue;
+        }
+        let start = open
+            .iter()
+            .rposition(|(open_markup, _)| *open_markup == markup)
+            .map_or(0, |index| open.remove(index).1);
+        spans.push(Span {
+            markup,
+            start,
+            end: words.trim_end().len(),
+        });
+    }
+
+    let length = words.trim_end().len();
+    words.truncate(length);
+    for (markup, start) in open {
+        spans.push(Span {
+            markup,
+            start,
+            end: length,
+        });
+    }
+    for span in spans.iter_mut() {
+        span.start = span.start.min(length);
+        span.end = span.end.min(length);
+    }
+    spans.retain(|span| matches!(span.markup, Markup::Footnote(_)) || span.start < span.end);
+    // Outer spans first, and spans over the same words in the order they close.
+    spans.sort_by_key(|span| (span.start, Reverse(span.end)));
+    (words, spans)
+}
+
+/// Renders verse text with its spans in a text style.
+pub fn render(text: &str, spans: &[Span], style: TextStyle) -> String {
+    match style {
+        TextStyle::Plain => text.to_string(),
+        TextStyle::Brackets | TextStyle::Markup => insert_marks(text, spans, style),
+        TextStyle::Unicode => text
+            .char_indices()
+            .map(|(index, c)| {
+                let within = |markup: Markup| {
+                    spans.iter().any(|span| {
+                        span.markup == markup && (span.start..span.end).contains(&index)
+                    })
+                };
+                styled_char(c, within(Markup::Supplied), within(Markup::WordsOfJesus))
+            })
+            .collect(),
+    }
+}
+
+// Puts the notation for each span into the text, closing inner spans before outer ones.  Spans
+// over the same words are inner first, as `parse` orders them.
+fn insert_marks(text: &str, spans: &[Span], style: TextStyle) -> String {
+    // Position, then closing marks before footnotes before opening marks, then nesting order.
+    let mut marks: Vec<(usize, u8, usize, usize, String)> = Vec::new();
+    for (index, span) in spans.iter().enumerate() {
+        let length = span.end - span.start;
+        let (open, close) = match &span.markup {
+            Markup::Supplied => ("[", "]"),
+            Markup::WordsOfJesus => ("‹", "›"),
+            Markup::Footnote(note) => {
+                if style == TextStyle::Markup {
+                    marks.push((span.start, 1, 0, index, format!("{{{}}}", escape(note))));
+                }
+                continue;
+            }
+        };
+        marks.push((span.end, 0, length, index, close.to_string()));
+        marks.push((
+            span.start,
+            2,
+            usize::MAX - length,
+            usize::MAX - index,
+            open.to_string(),
+        ));
+    }
+    marks.sort();
+
+    // Only markup is read back, so only it needs the words escaped.
+    let words = |text: &str| match style {
+        TextStyle::Markup => escape(text),
+        _ => text.to_string(),
+    };
+    let mut rendered = String::with_capacity(text.len() + marks.len() * 2);
+    let mut position = 0;
+    for (at, _, _, _, mark) in marks {
+        rendered.push_str(&words(&text[position..at]));
+        rendered.push_str(&mark);
+        position = at;
+    }
+    rendered.push_str(&words(&text[position..]));
+    rendered
+}
+
+// Mathematical sans-serif letters, which chat shows in italic, bold or both.
+fn styled_char(c: char, italic: bool, bold: bool) -> char {
+    let (upper, lower) = match (italic, bold) {
+        (true, true) => (0x1D63C, 0x1D656),
+        (true, false) => (0x1D608, 0x1D622),
+        (false, true) => (0x1D5D4, 0x1D5EE),
+        (false, false) => return c,
+    };
+    let code = match c {
+        'A'..='Z' => upper + (c as u32 - 'A' as u32),
+        'a'..='z' => lower + (c as u32 - 'a' as u32),
+        _ => return c,
+    };
+    char::from_u32(code).unwrap_or(c)
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn parse_markup() {
+        let (words, spans) = parse("‹I [am] the  bread of life:›{Or, food} he that cometh ");
+        assert_eq!(words, "I am the bread of life: he that cometh");
+        assert_eq!(
+            spans,
+            [
+                Span {
+                    markup: Markup::WordsOfJesus,
+                    start: 0,
+                    end: 23
+                },
+                Span {
+                    markup: Markup::Supplied,
+                    start: 2,
+                    end: 4
+                },
+                Span {
+                    markup: Markup::Footnote("Or, food".to_string()),
+                    start: 23,
+                    end: 23
+                },
+            ]
+        );
+
+        // Quotes carried over from the verse before or on to the next.
+        let (words, spans) = parse("whosoever believeth in him.› And");
+        assert_eq!((spans[0].start, spans[0].end), (0, 27));
+        assert_eq!(&words[..27], "whosoever believeth in him.");
+        let (_, spans) = parse("Jesus answered, ‹Verily");
+        assert_eq!((spans[0].start, spans[0].end), (16, 22));
+
+        assert_eq!(parse("no markup"), ("no markup".to_string(), Vec::new()));
+    }
+
+    #[test]
+    fn render_styles() {
+        let marked = "‹I [am] the bread of life:›{Or, food} he";
+        let (words, spans) = parse(marked);
+        assert_eq!(render(&words, &spans, TextStyle::Plain), words);
+        assert_eq!(
+            render(&words, &spans, TextStyle::Brackets),
+            "‹I [am] the bread of life:› he"
+        );
+        assert_eq!(render(&words, &spans, TextStyle::Markup), marked);
+        assert_eq!(
+            render(&words, &spans, TextStyle::Unicode),
+            "𝗜 𝙖𝙢 𝘁𝗵𝗲 𝗯𝗿𝗲𝗮𝗱 𝗼𝗳 𝗹𝗶𝗳𝗲: he"
+        );
+
+        let (words, spans) = parse("[‹Amen›]");
+        assert_eq!(render(&words, &spans, TextStyle::Markup), "[‹Amen›]");
+
+        // Brackets that are part of the words are escaped in markup and left alone otherwise.
+        let marked = format!("{}[is]{{See \\{{1\\}}}}", escape("[A]mp {b} "));
+        let (words, spans) = parse(&marked);
+        assert_eq!(words, "[A]mp {b} is");
+        assert_eq!(spans[1].markup, Markup::Footnote("See {1}".to_string()));
+        assert_eq!(render(&words, &spans, TextStyle::Markup), marked);
+        assert_eq!(
+            render(&words, &spans, TextStyle::Brackets),
+            "[A]mp {b} [is]"
+        );
+
+        assert_eq!("Bracket".parse(), Ok(TextStyle::Brackets));
+        assert!("red".parse::<TextStyle>().is_err());
+    }
+}
@@ -1 +1,13 @@
 pub mod bible;
+pub mod books;
+pub mod compare;
+pub mod cross_references;
+pub mod diff;
+pub mod markup;
+pub mod outline;
+pub mod plan;
+pub mod reference;
+pub mod scope;
+pub mod search;
+pub mod strongs;
+pub mod versification;
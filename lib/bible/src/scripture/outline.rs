@@ -0,0 +1,72 @@
+use crate::scripture::bible::Verse;
+
+/// A section of a passage, from the verse with its heading to the verse before the next.
+#[derive(Debug, PartialEq)]
+pub struct Section<'a> {
+    pub headings: &'a [String],
+    pub first: &'a Verse,
+    pub last: &'a Verse,
+}
+
+impl Section<'_> {
+    /// The verses of the section, e.g. 1-11, or 8:31-39 and 8:39-9:5 when `chapters` is set.
+    pub fn verse_range(&self, chapters: bool) -> String {
+        let (first, last) = (self.first, self.last);
+        let start = if chapters {
+            format!("{}:{}", first.chapter, first.verse)
+        } else {
+            first.verse.to_string()
+        };
+        if first.id() == last.id() {
+            start
+        } else if first.chapter != last.chapter {
+            format!("{}-{}:{}", start, last.chapter, last.verse)
+        } else {
+            format!("{}-{}", start, last.verse)
+        }
+    }
+}
+
+/// The sections of a passage by their headings, so Romans 8 is "Life in the Spirit" at 1-17
+/// and so on.  Verses before the first heading, which belong to a section that started before
+/// the passage, are left out.
+pub fn outline(verses: &[Verse]) -> Vec<Section<'_>> {
+    let starts: Vec<usize> = verses
+        .iter()
+        .enumerate()
+        .filter(|(_, verse)| !verse.headings.is_empty())
+        .map(|(index, _)| index)
+        .collect();
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain([&verses.len()]))
+        .map(|(&start, &next)| Section {
+            headings: &verses[start].headings,
+            first: &verses[start],
+            last: &verses[next - 1],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn outline_sections() {
+        let mut verses: Vec<Verse> = (1..=5)
+            .map(|verse| Verse::new(45, 8, verse, format!("Romans 8:{}", verse)))
+            .chain([Verse::new(45, 9, 1, "Romans 9:1".to_string())])
+            .collect();
+        verses[1].headings = vec!["Life in the Spirit".to_string()];
+        verses[4].headings = vec!["God's Love in Christ".to_string()];
+
+        let sections = outline(&verses);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].headings, ["Life in the Spirit"]);
+        assert_eq!(sections[0].verse_range(false), "2-4");
+        assert_eq!(sections[1].verse_range(true), "8:5-9:1");
+        assert_eq!(outline(&verses[5..]).len(), 0);
+        assert_eq!(outline(&verses[4..5])[0].verse_range(true), "8:5");
+    }
+}
@@ -0,0 +1,270 @@
+use crate::scripture::books::Book;
+use crate::scripture::reference::{Reference, ReferenceError};
+
+/// A reading schedule with the passages to read on each day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadingPlan {
+    /// The short name a plan is started by, e.g. nt90.
+    pub name: String,
+    pub title: String,
+    pub days: Vec<Vec<Reference>>,
+}
+
+// A run of whole chapters of one book as book, first chapter and last chapter.
+type Segment = (u8, u8, u8);
+
+// The order events happened in, by book with a few books split where others fit between them.
+const CHRONOLOGICAL: &[Segment] = &[
+    (1, 1, 11),
+    (18, 1, 42),
+    (1, 12, 50),
+    (2, 1, 40),
+    (3, 1, 27),
+    (4, 1, 36),
+    (5, 1, 34),
+    (6, 1, 24),
+    (7, 1, 21),
+    (8, 1, 4),
+    (9, 1, 31),
+    (10, 1, 24),
+    (13, 1, 29),
+    (19, 1, 150),
+    (11, 1, 11),
+    (20, 1, 31),
+    (21, 1, 12),
+    (22, 1, 8),
+    (14, 1, 9),
+    (11, 12, 22),
+    (12, 1, 17),
+    (32, 1, 4),
+    (30, 1, 9),
+    (28, 1, 14),
+    (33, 1, 7),
+    (23, 1, 66),
+    (12, 18, 25),
+    (14, 10, 36),
+    (34, 1, 3),
+    (36, 1, 3),
+    (35, 1, 3),
+    (24, 1, 52),
+    (25, 1, 5),
+    (31, 1, 1),
+    (29, 1, 3),
+    (26, 1, 48),
+    (27, 1, 12),
+    (15, 1, 6),
+    (37, 1, 2),
+    (38, 1, 14),
+    (17, 1, 10),
+    (15, 7, 10),
+    (16, 1, 13),
+    (39, 1, 4),
+    (40, 1, 28),
+    (41, 1, 16),
+    (42, 1, 24),
+    (43, 1, 21),
+    (44, 1, 14),
+    (59, 1, 5),
+    (48, 1, 6),
+    (44, 15, 18),
+    (52, 1, 5),
+    (53, 1, 3),
+    (44, 19, 19),
+    (46, 1, 16),
+    (47, 1, 13),
+    (45, 1, 16),
+    (44, 20, 28),
+    (49, 1, 6),
+    (50, 1, 4),
+    (51, 1, 4),
+    (57, 1, 1),
+    (54, 1, 6),
+    (56, 1, 3),
+    (60, 1, 5),
+    (58, 1, 13),
+    (55, 1, 4),
+    (61, 1, 3),
+    (65, 1, 1),
+    (62, 1, 5),
+    (63, 1, 1),
+    (64, 1, 1),
+    (66, 1, 22),
+];
+
+impl ReadingPlan {
+    /// A plan read from a list of references for each day, e.g. from a plan file.
+    pub fn from_days(
+        name: &str,
+        title: &str,
+        days: &[Vec<String>],
+    ) -> Result<Self, ReferenceError> {
+        let days = days
+            .iter()
+            .map(|day| {
+                day.iter()
+                    .map(|text| {
+                        let reference: Reference = text.parse()?;
+                        for location in [reference.start, reference.end] {
+                            if let Some(book) = Book::get(location.book) {
+                                book.check_verse(location.chapter, location.verse.unwrap_or(1))?;
+                            }
+                        }
+                        Ok(reference)
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            days,
+        })
+    }
+
+    /// The plans that come with ChapterVerse.
+    pub fn bundled() -> Vec<ReadingPlan> {
+        vec![
+            Self::mcheyne(),
+            Self::chronological(),
+            Self::new_testament_in_90_days(),
+        ]
+    }
+
+    /// Four readings a day after Robert Murray M'Cheyne's calendar, through the Old Testament
+    /// once and the New Testament and Psalms twice in a year.
+    pub fn mcheyne() -> Self {
+        let tracks = [
+            books(1, 18),
+            [books(40, 66), books(19, 19)].concat(),
+            books(19, 39),
+            [books(44, 66), books(40, 43)].concat(),
+        ]
+        .map(|track| spread(&track, 365));
+        let days = (0..365)
+            .map(|day| tracks.iter().flat_map(|track| track[day].clone()).collect())
+            .collect();
+        Self {
+            name: "mcheyne".to_string(),
+            title: "M'Cheyne".to_string(),
+            days,
+        }
+    }
+
+    /// The whole Bible in a year, in the order the events happened.
+    pub fn chronological() -> Self {
+        Self {
+            name: "chronological".to_string(),
+            title: "Chronological".to_string(),
+            days: spread(CHRONOLOGICAL, 365),
+        }
+    }
+
+    pub fn new_testament_in_90_days() -> Self {
+        Self {
+            name: "nt90".to_string(),
+            title: "New Testament in 90 Days".to_string(),
+            days: spread(&books(40, 66), 90),
+        }
+    }
+
+    /// The passages for a day of the plan, counting from 1.
+    pub fn day(&self, day: usize) -> Option<&[Reference]> {
+        self.days.get(day.checked_sub(1)?).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+}
+
+fn books(first: u8, last: u8) -> Vec<Segment> {
+    (first..=last)
+        .filter_map(Book::get)
+        .map(|book| (book.id, 1, book.chapters()))
+        .collect()
+}
+
+// Shares the chapters out over the days as evenly as whole chapters allow, with the chapters of
+// a day in a row read as one passage such as Genesis 1-3.
+fn spread(segments: &[Segment], days: usize) -> Vec<Vec<Reference>> {
+    let chapters: Vec<(u8, u8)> = segments
+        .iter()
+        .flat_map(|&(book, first, last)| (first..=last).map(move |chapter| (book, chapter)))
+        .collect();
+    let mut plan = vec![Vec::new(); days];
+    for (index, (book, chapter)) in chapters.iter().copied().enumerate() {
+        let day: &mut Vec<Reference> = &mut plan[index * days / chapters.len()];
+        match day.last_mut() {
+            Some(passage) if passage.end.book == book && passage.end.chapter + 1 == chapter => {
+                passage.end.chapter = chapter;
+            }
+            _ => day.push(Reference::chapter(book, chapter)),
+        }
+    }
+    plan
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+    use crate::scripture::books::Canon;
+    use std::collections::HashMap;
+
+    // How many times each chapter is read over the whole plan.
+    fn chapter_readings(plan: &ReadingPlan) -> HashMap<(u8, u8), usize> {
+        let mut readings = HashMap::new();
+        for reference in plan.days.iter().flatten() {
+            for chapter in reference.start.chapter..=reference.end.chapter {
+                *readings.entry((reference.start.book, chapter)).or_default() += 1;
+            }
+        }
+        readings
+    }
+
+    #[test]
+    fn bundled_plans_cover_their_books() {
+        let chronological = ReadingPlan::chronological();
+        assert_eq!(chronological.len(), 365);
+        let readings = chapter_readings(&chronological);
+        assert_eq!(readings.len(), 1189);
+        assert!(readings.values().all(|count| *count == 1));
+        assert!(Canon::Protestant
+            .books()
+            .all(|book| readings.contains_key(&(book.id, book.chapters()))));
+
+        let mcheyne = ReadingPlan::mcheyne();
+        let readings = chapter_readings(&mcheyne);
+        assert_eq!(readings[&(1, 50)], 1);
+        assert_eq!(readings[&(19, 119)], 2);
+        assert_eq!(readings[&(66, 22)], 2);
+        assert_eq!(mcheyne.day(1).unwrap()[0].to_string(), "Genesis 1-2");
+
+        let nt = ReadingPlan::new_testament_in_90_days();
+        assert_eq!(nt.len(), 90);
+        assert_eq!(nt.day(1).unwrap()[0].to_string(), "Matthew 1-3");
+        assert_eq!(nt.day(90).unwrap().last().unwrap().end.chapter, 22);
+        assert!(nt.day(0).is_none() && nt.day(91).is_none());
+    }
+
+    #[test]
+    fn plans_from_days() {
+        let days = vec![
+            vec!["Genesis 1".to_string(), "Psalm 1".to_string()],
+            vec!["John 1:1-18".to_string()],
+        ];
+        let plan = ReadingPlan::from_days("short", "Short", &days).unwrap();
+        assert_eq!(plan.day(2).unwrap()[0].to_string(), "John 1:1-18");
+
+        let days = vec![vec!["Jude 2".to_string()]];
+        assert_eq!(
+            ReadingPlan::from_days("bad", "Bad", &days)
+                .unwrap_err()
+                .to_string(),
+            "Jude only has 1 chapter"
+        );
+    }
+}
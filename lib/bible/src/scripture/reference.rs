@@ -0,0 +1,612 @@
+use crate::scripture::books::{name_pattern, Book, Language, BOOKS};
+use regex::{Captures, Regex};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// A point in scripture.  A missing verse means the whole chapter: the first verse when it starts
+/// a reference and the last verse when it ends one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub book: u8,
+    pub chapter: u8,
+    pub verse: Option<u8>,
+}
+
+/// A parsed scripture reference such as John 3:16, John 3:16-18, Psalm 23, Matthew 5-7 or
+/// Malachi 4:6-Matthew 1:1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reference {
+    pub start: Location,
+    pub end: Location,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceError {
+    NotAReference(String),
+    UnknownBook(String),
+    BookNotInTranslation(String),
+    ChapterOutOfRange {
+        book: String,
+        chapter: u8,
+        chapters: u8,
+    },
+    VerseOutOfRange {
+        book: String,
+        chapter: u8,
+        verse: u8,
+        verses: u8,
+    },
+    InvalidRange(String),
+}
+
+impl fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReferenceError::NotAReference(text) => {
+                write!(f, "\"{}\" is not a scripture reference", text)
+            }
+            ReferenceError::UnknownBook(book) => write!(f, "{} is not a book of the Bible", book),
+            ReferenceError::BookNotInTranslation(book) => {
+                write!(f, "{} is not in this translation", book)
+            }
+            ReferenceError::ChapterOutOfRange { book, chapters, .. } => match chapters {
+                1 => write!(f, "{} only has 1 chapter", book),
+                _ => write!(f, "{} only has {} chapters", book, chapters),
+            },
+            ReferenceError::VerseOutOfRange {
+                book,
+                chapter,
+                verses,
+                ..
+            } => write!(f, "{} {} only has {} verses", book, chapter, verses),
+            ReferenceError::InvalidRange(reference) => {
+                write!(f, "{} ends before it starts", reference)
+            }
+        }
+    }
+}
+
+impl Error for ReferenceError {}
+
+// Book C or Book C:V, optionally followed by -V, -C, -C:V, -Book C or -Book C:V.
+fn reference_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| reference_pattern(":"))
+}
+
+// References written the European way with a comma between chapter and verse, e.g.
+// "Johannes 3,16" or "1. Mose 1,1-3".  Used for book names that are not English and for any
+// chapter and verse written with a comma and no space, as in "Joh 3,16" or "Psalm 23,1".
+fn european_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| reference_pattern(":,"))
+}
+
+// Known book names are tried longest first so multi-word names such as "Song of Solomon" or
+// "First John" are read whole.  Any other whole word is still captured so a misspelt book can
+// be corrected or reported, but never the tail of a word such as the "ames" of "games".  The
+// end of a range only takes a known book, so "Genesis 1:1-3 and 5" ends at verse 3.
+fn reference_pattern(separators: &str) -> Regex {
+    let book = format!(
+        r"(\b(?:{})|(?:\b\d\.?\s?\p{{L}}+|\b\p{{L}}+)\s?\d?)",
+        name_pattern()
+    );
+    let end_book = format!(r"(\b(?:{}))", name_pattern());
+    Regex::new(&format!(
+        r"(?i){book}\s(\d+)(?:[{s}](\d+))?(?:\s?-\s?(?:{end_book}\s(\d+)(?:[{s}](\d+))?|(\d+)(?:[{s}](\d+))?))?",
+        book = book,
+        end_book = end_book,
+        s = separators
+    ))
+    .expect("Invalid regex pattern")
+}
+
+// The reference at or after `start`, and whether it is written the European way.  Many
+// German abbreviations such as "Joh" or "Mt" are English too, so a verse after a comma is
+// enough, while a spaced list such as "Psalm 23, 24" is still read as chapters.
+fn captures_at(text: &str, start: usize) -> Option<(Captures<'_>, bool)> {
+    let caps = european_regex().captures_at(text, start)?;
+    let comma_verse = caps
+        .get(3)
+        .is_some_and(|verse| text[..verse.start()].ends_with(','));
+    let european = comma_verse
+        || caps
+            .get(1)
+            .and_then(|book| Book::find_with_language(book.as_str()))
+            .is_some_and(|(_, language)| language != Language::English);
+    if european {
+        Some((caps, true))
+    } else {
+        reference_regex()
+            .captures_at(text, start)
+            .map(|caps| (caps, false))
+    }
+}
+
+// Further chapters or verses listed after a reference, e.g. the ", 18-20" or "; 4:1" of
+// "John 3:16, 18-20; 4:1".
+fn list_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"(?i)^\s?[,;]\s?(?:and\s)?(\d+)(?::(\d+))?(?:\s?-\s?(\d+)(?::(\d+))?)?")
+            .expect("Invalid regex pattern")
+    })
+}
+
+// The same for European references, where a full stop separates verses and a semicolon
+// chapters, e.g. the ".18" and "; 4,1" of "Johannes 3,16.18; 4,1".
+fn european_list_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^(?:\.|\s?;\s?)(\d+)(?:,(\d+))?(?:\s?-\s?(\d+)(?:,(\d+))?)?")
+            .expect("Invalid regex pattern")
+    })
+}
+
+impl Location {
+    pub fn new(book: u8, chapter: u8, verse: Option<u8>) -> Self {
+        Self {
+            book,
+            chapter,
+            verse,
+        }
+    }
+}
+
+impl Reference {
+    pub fn new(start: Location, end: Location) -> Self {
+        Self { start, end }
+    }
+
+    pub fn verse(book: u8, chapter: u8, verse: u8) -> Self {
+        let location = Location::new(book, chapter, Some(verse));
+        Self::new(location, location)
+    }
+
+    pub fn chapter(book: u8, chapter: u8) -> Self {
+        let location = Location::new(book, chapter, None);
+        Self::new(location, location)
+    }
+
+    pub fn book_name(&self) -> &'static str {
+        book_name(self.start.book)
+    }
+
+    /// Every reference found in free text such as a chat message, in the order given.  Words
+    /// that are not books are skipped, so "I have 2 questions about John 3:16" finds John 3:16.
+    /// Whole chapters such as "Psalm 23" are only read when the text holds nothing else, so
+    /// chat such as "John 5 times" is left alone.
+    pub fn parse_all(text: &str) -> Vec<Reference> {
+        let mut references = Vec::new();
+        let mut search_from = 0;
+        // The end of the text read as references so far, and whether anything else was said.
+        let mut read_to = 0;
+        let mut other_text = false;
+        while let Some((caps, european)) = captures_at(text, search_from) {
+            let found = caps.get(0).map_or(text.len()..text.len(), |m| m.range());
+            let book_abbr = caps.get(1).map_or("", |m| m.as_str()).trim();
+
+            // Chapter-only references need more than a two letter abbreviation, otherwise
+            // ordinary chat such as "I am 5 minutes late" would be read as Amos 5.
+            let short_chapter_only = caps.get(3).is_none()
+                && book_abbr.chars().filter(|c| c.is_alphabetic()).count() < 3;
+
+            match Self::from_captures(&caps) {
+                Ok(reference) if !short_chapter_only => {
+                    other_text |= text[read_to..found.start].contains(char::is_alphanumeric);
+                    references.push(reference);
+                    search_from =
+                        Self::parse_list(text, found.end, reference, &mut references, european);
+                    read_to = search_from;
+                }
+                Err(ReferenceError::UnknownBook(_)) => {
+                    // Look again from the next word in case this match swallowed the start of
+                    // a real reference, e.g. "read 1 john 3:16".
+                    let rest = &text[found.start..];
+                    search_from = found.start
+                        + rest
+                            .find(|c: char| !c.is_alphanumeric())
+                            .filter(|&end| end > 0)
+                            .unwrap_or_else(|| rest.chars().next().map_or(1, char::len_utf8));
+                }
+                _ => search_from = found.end,
+            }
+        }
+        if other_text || text[read_to..].contains(char::is_alphanumeric) {
+            references.retain(|reference| reference.start.verse.is_some());
+        }
+        references
+    }
+
+    fn from_captures(caps: &Captures) -> Result<Self, ReferenceError> {
+        let text = caps.get(0).map_or("", |m| m.as_str());
+        let number = |group: usize| caps.get(group).and_then(|m| m.as_str().parse::<u8>().ok());
+        let invalid = || ReferenceError::NotAReference(text.to_string());
+
+        let book_text = caps.get(1).map_or("", |m| m.as_str()).trim();
+        // A misspelt book is only corrected when a verse follows, as in "Mathew 5:9", so chat
+        // such as "park 2" is not read as Mark 2.
+        let book = match (book_id(book_text), caps.get(3)) {
+            (Err(e), Some(_)) => Book::find_fuzzy(book_text).map(|book| book.id).ok_or(e)?,
+            (result, _) => result?,
+        };
+        let chapter = number(2).ok_or_else(invalid)?;
+        let verse = match caps.get(3) {
+            Some(_) => Some(number(3).ok_or_else(invalid)?),
+            None => None,
+        };
+        let start = Location::new(book, chapter, verse);
+
+        // The end of the range may name another book, another chapter, or just a verse.
+        let end = match caps.get(4) {
+            Some(end_book) => Location::new(
+                book_id(end_book.as_str().trim())?,
+                number(5).ok_or_else(invalid)?,
+                number(6),
+            ),
+            None => match (number(7), number(8)) {
+                (Some(end_chapter), Some(end_verse)) => {
+                    Location::new(book, end_chapter, Some(end_verse))
+                }
+                (Some(end_verse), None) if verse.is_some() => {
+                    Location::new(book, chapter, Some(end_verse))
+                }
+                (Some(end_chapter), None) => Location::new(book, end_chapter, None),
+                (None, _) if caps.get(7).is_some() => return Err(invalid()),
+                (None, _) => start,
+            },
+        };
+        Ok(Self::new(start, end))
+    }
+
+    /// Adds the chapters or verses listed after a reference, such as the 18 and 21 of
+    /// "John 3:16,18,21", returning where the list ends in the text.
+    fn parse_list(
+        text: &str,
+        mut search_from: usize,
+        previous: Reference,
+        references: &mut Vec<Reference>,
+        european: bool,
+    ) -> usize {
+        let book = previous.end.book;
+        let mut chapter = previous.end.chapter;
+        let mut verse_list = previous.start.verse.is_some();
+
+        let list_regex = if european {
+            european_list_regex()
+        } else {
+            list_regex()
+        };
+        while let Some(item) = list_regex.captures(&text[search_from..]) {
+            // Stop at the next full reference, e.g. the "1" of "John 3:16, 1 John 4:8".
+            let item_start = search_from + item.get(1).map_or(0, |m| m.start());
+            if reference_regex()
+                .captures_at(text, item_start)
+                .is_some_and(|next| {
+                    next.get(0).is_some_and(|m| m.start() == item_start)
+                        && next
+                            .get(1)
+                            .is_some_and(|m| book_id(m.as_str().trim()).is_ok())
+                })
+            {
+                break;
+            }
+
+            let number = |group: usize| item.get(group).and_then(|m| m.as_str().parse::<u8>().ok());
+            let reference = match (number(1), number(2), number(3), number(4)) {
+                (Some(start_chapter), Some(start_verse), end_chapter, end_verse) => {
+                    verse_list = true;
+                    let end = match (end_chapter, end_verse) {
+                        (Some(end_chapter), Some(end_verse)) => (end_chapter, end_verse),
+                        (Some(end_verse), None) => (start_chapter, end_verse),
+                        _ => (start_chapter, start_verse),
+                    };
+                    Reference::new(
+                        Location::new(book, start_chapter, Some(start_verse)),
+                        Location::new(book, end.0, Some(end.1)),
+                    )
+                }
+                (Some(start_verse), None, end_chapter, end_verse) if verse_list => {
+                    let end = match (end_chapter, end_verse) {
+                        (Some(end_chapter), Some(end_verse)) => (end_chapter, end_verse),
+                        (Some(end_verse), None) => (chapter, end_verse),
+                        _ => (chapter, start_verse),
+                    };
+                    Reference::new(
+                        Location::new(book, chapter, Some(start_verse)),
+                        Location::new(book, end.0, Some(end.1)),
+                    )
+                }
+                (Some(start_chapter), None, end_chapter, end_verse) => {
+                    let end = match (end_chapter, end_verse) {
+                        (Some(end_chapter), end_verse) => {
+                            Location::new(book, end_chapter, end_verse)
+                        }
+                        _ => Location::new(book, start_chapter, None),
+                    };
+                    Reference::new(Location::new(book, start_chapter, None), end)
+                }
+                _ => break,
+            };
+
+            chapter = reference.end.chapter;
+            references.push(reference);
+            search_from += item.get(0).map_or(0, |m| m.end());
+        }
+        search_from
+    }
+}
+
+impl FromStr for Reference {
+    type Err = ReferenceError;
+
+    /// Parses text that is exactly one reference, e.g. "2 tim 3:16-17".
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        match captures_at(text, 0) {
+            Some((caps, _)) if caps.get(0).is_some_and(|m| m.range() == (0..text.len())) => {
+                Self::from_captures(&caps)
+            }
+            _ => Err(ReferenceError::NotAReference(text.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (start, end) = (self.start, self.end);
+        write!(f, "{} {}", book_name(start.book), start.chapter)?;
+        if let Some(verse) = start.verse {
+            write!(f, ":{}", verse)?;
+        }
+        if end == start {
+            return Ok(());
+        }
+
+        write!(f, "-")?;
+        if end.book != start.book {
+            write!(f, "{} ", book_name(end.book))?;
+        } else if let (Some(_), Some(verse), true) =
+            (start.verse, end.verse, end.chapter == start.chapter)
+        {
+            return write!(f, "{}", verse);
+        }
+        write!(f, "{}", end.chapter)?;
+        match end.verse {
+            Some(verse) => write!(f, ":{}", verse),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The short name of a book id used in abbreviated references, e.g. Jn for 43.
+pub fn book_abbreviation(book: u8) -> &'static str {
+    Book::get(book).map_or("", |book| book.abbreviation)
+}
+
+/// The book id for an OSIS book code, e.g. 43 for John.
+pub fn osis_book_id(code: &str) -> Option<u8> {
+    BOOKS
+        .iter()
+        .find(|book| book.osis.eq_ignore_ascii_case(code))
+        .map(|book| book.id)
+}
+
+/// The book id for a USFM book code, e.g. 43 for JHN.
+pub fn usfm_book_id(code: &str) -> Option<u8> {
+    BOOKS
+        .iter()
+        .find(|book| book.usfm.eq_ignore_ascii_case(code))
+        .map(|book| book.id)
+}
+
+/// The canonical name for a book id, e.g. 43 is John.
+pub fn book_name(book: u8) -> &'static str {
+    Book::get(book).map_or("", |book| book.name)
+}
+
+/// The book id for any recognised name or abbreviation, e.g. "jn" is 43.
+pub fn book_id(abbreviation: &str) -> Result<u8, ReferenceError> {
+    Book::find(abbreviation)
+        .map(|book| book.id)
+        .ok_or_else(|| ReferenceError::UnknownBook(abbreviation.to_string()))
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        for (text, expected) in [
+            ("jn 3:16", "John 3:16"),
+            ("john 3:16-18", "John 3:16-18"),
+            ("John 3:36-4:3", "John 3:36-4:3"),
+            ("Mal 4:6 - Matt 1:1", "Malachi 4:6-Matthew 1:1"),
+            ("psalms 23", "Psalm 23"),
+            ("Matt 5-7", "Matthew 5-7"),
+            ("John 3-4:2", "John 3-4:2"),
+            ("Mal 4-Matt 1", "Malachi 4-Matthew 1"),
+        ] {
+            let reference: Reference = text.parse().unwrap();
+            assert_eq!(reference.to_string(), expected);
+            assert_eq!(expected.parse::<Reference>(), Ok(reference));
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "Mathew 5".parse::<Reference>(),
+            Err(ReferenceError::UnknownBook("Mathew".to_string()))
+        );
+        assert_eq!(
+            "Hezekiah 1:1".parse::<Reference>(),
+            Err(ReferenceError::UnknownBook("Hezekiah".to_string()))
+        );
+        assert_eq!(
+            "John 3:16 is great".parse::<Reference>(),
+            Err(ReferenceError::NotAReference(
+                "John 3:16 is great".to_string()
+            ))
+        );
+        assert_eq!(
+            ReferenceError::UnknownBook("Hezekiah".to_string()).to_string(),
+            "Hezekiah is not a book of the Bible"
+        );
+    }
+
+    #[test]
+    fn parse_multi_word_book_names() {
+        for (text, expected) in [
+            ("Song of Solomon 2:4", "Song of Solomon 2:4"),
+            ("song of songs 8:6-7", "Song of Solomon 8:6-7"),
+            ("First John 1:9", "1 John 1:9"),
+            ("Second Samuel 7", "2 Samuel 7"),
+            ("1st Kings 19:12", "1 Kings 19:12"),
+        ] {
+            assert_eq!(text.parse::<Reference>().unwrap().to_string(), expected);
+        }
+
+        let references: Vec<String> =
+            Reference::parse_all("Compare Song of Songs 2:4 with Second Samuel 7:12-2 Kings 1:1")
+                .iter()
+                .map(Reference::to_string)
+                .collect();
+        assert_eq!(
+            references,
+            ["Song of Solomon 2:4", "2 Samuel 7:12-2 Kings 1:1"]
+        );
+    }
+
+    #[test]
+    fn parse_localized_and_european_references() {
+        for (text, expected) in [
+            ("Juan 3:16", "John 3:16"),
+            ("Johannes 3,16", "John 3:16"),
+            ("1. Mose 1,1", "Genesis 1:1"),
+            ("Apocalipsis 21:4", "Revelation 21:4"),
+            ("Römer 8,28", "Romans 8:28"),
+            ("roemer 8,28-30", "Romans 8:28-30"),
+            ("1 Juan 1:9", "1 John 1:9"),
+            ("Johannes 3,36-4,2", "John 3:36-4:2"),
+            ("Mathew 5:9", "Matthew 5:9"),
+            ("Phillipians 4:13", "Philippians 4:13"),
+        ] {
+            assert_eq!(text.parse::<Reference>().unwrap().to_string(), expected);
+        }
+
+        let references: Vec<String> =
+            Reference::parse_all("Lies Johannes 3,16.18; 4,1 und Joh 5,24 und Hechos 2:38")
+                .iter()
+                .map(Reference::to_string)
+                .collect();
+        assert_eq!(
+            references,
+            [
+                "John 3:16",
+                "John 3:18",
+                "John 4:1",
+                "John 5:24",
+                "Acts 2:38"
+            ]
+        );
+        // A comma with no space before the verse is read the European way whatever the book's
+        // language, as German abbreviations are often English ones too.
+        for (text, expected) in [
+            ("John 3,16", "John 3:16"),
+            ("Joh 3,16", "John 3:16"),
+            ("Mt 5,3", "Matthew 5:3"),
+            ("Lk 2,14", "Luke 2:14"),
+            ("Gen 1,1", "Genesis 1:1"),
+            ("Psalm 23,1", "Psalm 23:1"),
+        ] {
+            let references: Vec<String> = Reference::parse_all(text)
+                .iter()
+                .map(Reference::to_string)
+                .collect();
+            assert_eq!(references, [expected], "{}", text);
+        }
+    }
+
+    #[test]
+    fn parse_all_with_lists() {
+        let references: Vec<String> = Reference::parse_all(
+            "read 1 jn 1:9 then John 3:16,18-20; 4:1, Rom 5:8 and Psalm 23, 24 but I am 5 minutes late",
+        )
+        .iter()
+        .map(Reference::to_string)
+        .collect();
+        assert_eq!(
+            references,
+            [
+                "1 John 1:9",
+                "John 3:16",
+                "John 3:18-20",
+                "John 4:1",
+                "Romans 5:8"
+            ]
+        );
+        let references: Vec<String> = Reference::parse_all("Psalm 23, 24")
+            .iter()
+            .map(Reference::to_string)
+            .collect();
+        assert_eq!(references, ["Psalm 23", "Psalm 24"]);
+    }
+
+    #[test]
+    fn parse_all_chapters_only_on_their_own() {
+        for text in [
+            "John 5 times",
+            "mark 2 things",
+            "I read John 3 and 1 John 2",
+        ] {
+            assert_eq!(Reference::parse_all(text), [], "{}", text);
+        }
+        assert_eq!(
+            Reference::parse_all("John 3 and John 3:16")
+                .iter()
+                .map(Reference::to_string)
+                .collect::<Vec<_>>(),
+            ["John 3:16"]
+        );
+        assert_eq!(Reference::parse_all("John 3-4; 1 John 2").len(), 2);
+    }
+
+    #[test]
+    fn parse_all_ignores_unknown_words() {
+        for text in [
+            "Mathews 5:9",
+            "June 5:30",
+            "times 1:1",
+            "ames 3:1",
+            "games 1:2",
+        ] {
+            assert_eq!(Reference::parse_all(text), [], "{}", text);
+        }
+        assert_eq!(
+            Reference::parse_all("Mathew 5:9 and then 1jn 1:9")
+                .iter()
+                .map(Reference::to_string)
+                .collect::<Vec<_>>(),
+            ["Matthew 5:9", "1 John 1:9"]
+        );
+    }
+
+    #[test]
+    fn parse_all_with_words_after_a_range() {
+        for (text, expected) in [
+            ("Genesis 1:1-3 and 5", "Genesis 1:1-3"),
+            ("Eph 2:8-9 and 10", "Ephesians 2:8-9"),
+            ("Mal 4:5-Matt 1:2 and more", "Malachi 4:5-Matthew 1:2"),
+        ] {
+            let references: Vec<String> = Reference::parse_all(text)
+                .iter()
+                .map(Reference::to_string)
+                .collect();
+            assert_eq!(references, [expected], "{}", text);
+        }
+    }
+}
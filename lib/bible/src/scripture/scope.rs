@@ -0,0 +1,126 @@
+use crate::scripture::bible::VerseId;
+use crate::scripture::books::{Book, Testament};
+use crate::scripture::reference::ReferenceError;
+use std::fmt;
+use std::str::FromStr;
+
+// Named groups of books in a row, with the names they may be asked for by.
+const GROUPS: [(&str, &[&str], u8, u8); 4] = [
+    ("The Law", &["law", "torah", "pentateuch"], 1, 5),
+    ("The Prophets", &["prophets"], 23, 39),
+    ("The Gospels", &["gospels", "gospel"], 40, 43),
+    ("The Epistles", &["epistles", "letters"], 45, 65),
+];
+
+/// The part of scripture to draw from, e.g. for a random verse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Bible,
+    /// A testament, where the Old Testament includes the deuterocanonical books.
+    Testament(Testament),
+    /// A group of books in a row, such as the Gospels.
+    Group {
+        name: &'static str,
+        first: u8,
+        last: u8,
+    },
+    Book(u8),
+    Chapter(u8, u8),
+}
+
+impl Scope {
+    pub fn contains(&self, id: VerseId) -> bool {
+        match *self {
+            Scope::Bible => true,
+            Scope::Testament(testament) => {
+                Book::get(id.book()).is_some_and(|book| book.testament == testament)
+            }
+            Scope::Group { first, last, .. } => (first..=last).contains(&id.book()),
+            Scope::Book(book) => id.book() == book,
+            Scope::Chapter(book, chapter) => id.book() == book && id.chapter() == chapter,
+        }
+    }
+}
+
+/// Reads a scope as written after a command, e.g. "", "nt", "old testament", "gospels",
+/// "psalms" or "proverbs 3".
+impl FromStr for Scope {
+    type Err = ReferenceError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let written = text.trim();
+        let text = written.to_lowercase();
+        match text.as_str() {
+            "" | "bible" => return Ok(Scope::Bible),
+            "ot" | "old" | "old testament" => return Ok(Scope::Testament(Testament::Old)),
+            "nt" | "new" | "new testament" => return Ok(Scope::Testament(Testament::New)),
+            _ => (),
+        }
+        let group_name = text.strip_prefix("the ").unwrap_or(&text);
+        let group = GROUPS
+            .iter()
+            .find(|(_, names, ..)| names.contains(&group_name));
+        if let Some(&(name, _, first, last)) = group {
+            return Ok(Scope::Group { name, first, last });
+        }
+
+        // A trailing number is a chapter, unless it is the book's own number as in "1 john".
+        if let Some((name, chapter)) = text.rsplit_once(' ') {
+            if let (Some(book), Ok(chapter)) = (Book::find(name), chapter.parse::<u8>()) {
+                book.check_verse(chapter, 1)?;
+                return Ok(Scope::Chapter(book.id, chapter));
+            }
+        }
+        Book::find(&text)
+            .map(|book| Scope::Book(book.id))
+            .ok_or_else(|| ReferenceError::UnknownBook(written.to_string()))
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |book: u8| Book::get(book).map_or("", |book| book.name);
+        match self {
+            Scope::Bible => write!(f, "The Bible"),
+            Scope::Testament(testament) => write!(f, "The {}", testament),
+            Scope::Group { name, .. } => write!(f, "{}", name),
+            Scope::Book(book) => write!(f, "{}", name(*book)),
+            Scope::Chapter(book, chapter) => write!(f, "{} {}", name(*book), chapter),
+        }
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn parse_scopes() {
+        for (text, expected) in [
+            ("", "The Bible"),
+            ("NT", "The New Testament"),
+            ("old testament", "The Old Testament"),
+            ("gospels", "The Gospels"),
+            ("the gospel", "The Gospels"),
+            ("Psalms", "Psalm"),
+            ("proverbs 3", "Proverbs 3"),
+            ("1 john", "1 John"),
+            ("song of songs 2", "Song of Solomon 2"),
+        ] {
+            assert_eq!(text.parse::<Scope>().unwrap().to_string(), expected);
+        }
+        assert_eq!(
+            "proverbs 40".parse::<Scope>().unwrap_err().to_string(),
+            "Proverbs only has 31 chapters"
+        );
+        assert_eq!(
+            "Hezekiah".parse::<Scope>(),
+            Err(ReferenceError::UnknownBook("Hezekiah".to_string()))
+        );
+
+        let gospels: Scope = "gospels".parse().unwrap();
+        assert!(gospels.contains(VerseId::new(43, 3, 16)));
+        assert!(!gospels.contains(VerseId::new(44, 1, 1)));
+        assert!(Scope::Testament(Testament::Old).contains(VerseId::new(67, 1, 1)));
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+// Postings pack the verse position and the word's place in the verse into one u32, which keeps
+// the index to a few megabytes per translation.  Words past the 255th in a verse share the last
+// slot, so phrases are only matched within the first 255 words.
+const WORD_BITS: u32 = 8;
+const WORD_MASK: u32 = (1 << WORD_BITS) - 1;
+
+/// An inverted word index over a translation, supporting words, "quoted phrases", AND (the
+/// default between terms) and OR.
+#[derive(Default)]
+pub struct SearchIndex {
+    words: HashMap<String, Vec<u32>>,
+    verses: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Word(String),
+    Phrase(Vec<String>),
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An index from postings saved by `words`, covering `verses` verses.
+    pub(crate) fn from_words(words: HashMap<String, Vec<u32>>, verses: usize) -> Self {
+        Self { words, verses }
+    }
+
+    /// Each word with its postings, for saving the index.
+    pub(crate) fn words(&self) -> &HashMap<String, Vec<u32>> {
+        &self.words
+    }
+
+    /// Indexes the text of the verse stored at `position`.
+    pub fn insert(&mut self, position: usize, text: &str) {
+        for (word_number, word) in tokenize(text).enumerate() {
+            let posting = ((position as u32) << WORD_BITS) | (word_number as u32).min(WORD_MASK);
+            self.words.entry(word).or_default().push(posting);
+        }
+        self.verses = self.verses.max(position + 1);
+    }
+
+    /// Verse positions matching the query, best first.  Verses score higher for rarer words and
+    /// for containing the words of an unquoted query next to each other.
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        let mut scores: HashMap<u32, f32> = HashMap::new();
+        for group in parse_query(query) {
+            for (verse, score) in self.search_group(&group) {
+                let best = scores.entry(verse).or_insert(0.0);
+                *best = best.max(score);
+            }
+        }
+
+        let mut results: Vec<(u32, f32)> = scores.into_iter().collect();
+        results.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        results
+            .into_iter()
+            .map(|(verse, _)| verse as usize)
+            .collect()
+    }
+
+    // Verses containing every term of the group, with their scores.
+    fn search_group(&self, group: &[Term]) -> HashMap<u32, f32> {
+        let mut scores: Option<HashMap<u32, f32>> = None;
+        for term in group {
+            let weight = self.weight(term);
+            let matches = self.matches(term);
+            scores = Some(match scores {
+                None => matches
+                    .into_iter()
+                    .map(|(verse, count)| (verse, weight * count as f32))
+                    .collect(),
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(verse, score)| {
+                        matches
+                            .get(&verse)
+                            .map(|count| (verse, score + weight * *count as f32))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut scores = scores.unwrap_or_default();
+        // Reward verses where the words of an unquoted query appear together, so that
+        // be still and know finds Psalm 46:10 first.
+        let words: Vec<String> = group
+            .iter()
+            .filter_map(|term| match term {
+                Term::Word(word) => Some(word.clone()),
+                Term::Phrase(_) => None,
+            })
+            .collect();
+        if words.len() > 1 {
+            let together = self.matches(&Term::Phrase(words));
+            for (verse, score) in scores.iter_mut() {
+                if together.contains_key(verse) {
+                    *score *= 2.0;
+                }
+            }
+        }
+        scores
+    }
+
+    // How many times the term appears in each verse containing it.
+    fn matches(&self, term: &Term) -> HashMap<u32, u32> {
+        let mut matches = HashMap::new();
+        match term {
+            Term::Word(word) => {
+                for posting in self.words.get(word).into_iter().flatten() {
+                    *matches.entry(posting >> WORD_BITS).or_insert(0) += 1;
+                }
+            }
+            Term::Phrase(words) => {
+                let Some(first) = words.first().and_then(|word| self.words.get(word)) else {
+                    return matches;
+                };
+                let following: Vec<HashSet<u32>> = words[1..]
+                    .iter()
+                    .map(|word| {
+                        self.words
+                            .get(word)
+                            .map(|postings| postings.iter().copied().collect())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                for posting in first {
+                    let found = following.iter().enumerate().all(|(offset, postings)| {
+                        let word_number = (posting & WORD_MASK) + offset as u32 + 1;
+                        word_number < WORD_MASK
+                            && postings.contains(&((posting & !WORD_MASK) | word_number))
+                    });
+                    if found {
+                        *matches.entry(posting >> WORD_BITS).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    // Rarer words count for more, using inverse document frequency.
+    fn weight(&self, term: &Term) -> f32 {
+        let word_weight = |word: &String| {
+            let verses = self
+                .words
+                .get(word)
+                .map(|postings| {
+                    let mut verses: Vec<u32> = postings.iter().map(|p| p >> WORD_BITS).collect();
+                    verses.dedup();
+                    verses.len()
+                })
+                .unwrap_or(0);
+            (1.0 + self.verses as f32 / (1 + verses) as f32).ln()
+        };
+        match term {
+            Term::Word(word) => word_weight(word),
+            Term::Phrase(words) => words.iter().map(word_weight).sum::<f32>() * 2.0,
+        }
+    }
+}
+
+/// Lowercase words with apostrophes removed, so "LORD's" is indexed and searched as "lords".
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+}
+
+// Splits the query into OR groups of terms that must all match.  Quoted text is a phrase, and
+// OR (or |) starts a new group.  AND is the default and may be written out.
+fn parse_query(query: &str) -> Vec<Vec<Term>> {
+    let mut groups = vec![Vec::new()];
+    for (segment_number, segment) in query.split('"').enumerate() {
+        if segment_number % 2 == 1 {
+            let words: Vec<String> = tokenize(segment).collect();
+            if !words.is_empty() {
+                groups.last_mut().unwrap().push(Term::Phrase(words));
+            }
+            continue;
+        }
+        for token in segment.split_whitespace() {
+            match token {
+                "OR" | "|" => groups.push(Vec::new()),
+                "AND" | "&" => (),
+                _ => groups
+                    .last_mut()
+                    .unwrap()
+                    .extend(tokenize(token).map(Term::Word)),
+            }
+        }
+    }
+    groups.retain(|group| !group.is_empty());
+    groups
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    fn sample_index() -> SearchIndex {
+        let mut index = SearchIndex::new();
+        for (position, text) in [
+            "Be still, and know that I am God.",
+            "Know ye not that ye are the temple of God?",
+            "Charity never faileth.",
+            "And now abideth faith, hope, charity; but the greatest of these is charity.",
+            "The LORD's still small voice.",
+        ]
+        .iter()
+        .enumerate()
+        {
+            index.insert(position, text);
+        }
+        index
+    }
+
+    #[test]
+    fn parse_query_groups() {
+        assert_eq!(
+            parse_query("\"be still\" know OR charity"),
+            [
+                vec![
+                    Term::Phrase(vec!["be".to_string(), "still".to_string()]),
+                    Term::Word("know".to_string())
+                ],
+                vec![Term::Word("charity".to_string())]
+            ]
+        );
+    }
+
+    #[test]
+    fn search_words_phrases_and_or() {
+        let index = sample_index();
+        assert_eq!(index.search("still"), [0, 4]);
+        assert_eq!(index.search("\"still small\""), [4]);
+        assert_eq!(index.search("know god"), [0, 1]);
+        assert_eq!(index.search("be still and know"), [0]);
+        assert_eq!(index.search("charity"), [3, 2]);
+        assert_eq!(index.search("faileth OR lords"), [2, 4]);
+        assert!(index.search("\"god still\"").is_empty());
+    }
+}
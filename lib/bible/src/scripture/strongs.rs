@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::str::FromStr;
+
+// The lexicon that comes with ChapterVerse, with the most common and most asked about words.
+// A full Strong's dictionary in the same format can be imported over it.
+const EMBEDDED_LEXICON: &str = include_str!("../../strongs_lexicon.tsv");
+
+/// A number from Strong's concordance, H for the Hebrew of the Old Testament and G for the Greek
+/// of the New, e.g. G26 for agape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StrongsNumber {
+    Hebrew(u16),
+    Greek(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrongsError(pub String);
+
+impl fmt::Display for StrongsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a Strong's number, such as H430 or G26",
+            self.0
+        )
+    }
+}
+
+impl Error for StrongsError {}
+
+impl StrongsNumber {
+    /// The numbers in an attribute such as OSIS lemma="strong:G3588 strong:G2316" or USFM
+    /// strong="H1254a,H853".  Numbers without H or G, as Zefania writes them, are Hebrew in the
+    /// Old Testament and Greek elsewhere.
+    pub fn parse_all(attribute: &str, book: u8) -> Vec<StrongsNumber> {
+        attribute
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|token| {
+                let token = token.strip_prefix("strong:").unwrap_or(token);
+                if token.starts_with(|c: char| c.is_ascii_digit()) {
+                    let number = token.parse().ok()?;
+                    Some(match book {
+                        1..=39 => StrongsNumber::Hebrew(number),
+                        _ => StrongsNumber::Greek(number),
+                    })
+                } else {
+                    token.parse().ok()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Reads numbers written as G26, g0026, H7225a or strong:H07225, leaving out the letter some
+/// lexicons add to tell words of one number apart.
+impl FromStr for StrongsNumber {
+    type Err = StrongsError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let written = text.trim();
+        let error = || StrongsError(written.to_string());
+        let text = written.strip_prefix("strong:").unwrap_or(written);
+        let mut chars = text.chars();
+        let language = chars.next().ok_or_else(error)?;
+        let digits: String = chars.take_while(char::is_ascii_digit).collect();
+        let rest = &text[language.len_utf8() + digits.len()..];
+        if rest.len() > 1 || !rest.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(error());
+        }
+        let number = digits.parse().ok().filter(|number| *number > 0);
+        match (language.to_ascii_uppercase(), number) {
+            ('H', Some(number)) => Ok(StrongsNumber::Hebrew(number)),
+            ('G', Some(number)) => Ok(StrongsNumber::Greek(number)),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for StrongsNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StrongsNumber::Hebrew(number) => write!(f, "H{}", number),
+            StrongsNumber::Greek(number) => write!(f, "G{}", number),
+        }
+    }
+}
+
+/// A word or phrase of a verse and the Strong's numbers of the original words it translates,
+/// e.g. "In the beginning" is H7225.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedWord {
+    pub text: String,
+    pub strongs: Vec<StrongsNumber>,
+}
+
+/// An original language word as Strong's dictionary gives it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexiconEntry {
+    pub number: StrongsNumber,
+    pub lemma: String,
+    pub transliteration: String,
+    pub gloss: String,
+}
+
+impl fmt::Display for LexiconEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({}): {}",
+            self.number, self.lemma, self.transliteration, self.gloss
+        )
+    }
+}
+
+/// Strong's Hebrew and Greek dictionary, by number.
+#[derive(Debug, Default)]
+pub struct Lexicon {
+    entries: HashMap<StrongsNumber, LexiconEntry>,
+}
+
+impl Lexicon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn embedded() -> Self {
+        Self::from_reader(EMBEDDED_LEXICON.as_bytes()).expect("Invalid embedded lexicon")
+    }
+
+    pub fn import(lexicon_path: &str) -> Result<Self, Box<dyn Error>> {
+        Self::from_reader(File::open(lexicon_path)?)
+    }
+
+    /// Reads tab separated lines of number, lemma, transliteration and gloss, e.g. G26, ἀγάπη,
+    /// agapē and "love".  Lines starting with # are comments.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Box<dyn Error>> {
+        let mut tsv_reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .has_headers(false)
+            .flexible(true)
+            .quoting(false)
+            .comment(Some(b'#'))
+            .from_reader(reader);
+        let mut lexicon = Self::new();
+
+        for result in tsv_reader.records() {
+            let record = result?;
+            let field = |index| record.get(index).unwrap_or_default().trim().to_string();
+            lexicon.insert(LexiconEntry {
+                number: field(0).parse()?,
+                lemma: field(1),
+                transliteration: field(2),
+                gloss: field(3),
+            });
+        }
+        Ok(lexicon)
+    }
+
+    pub fn insert(&mut self, entry: LexiconEntry) {
+        self.entries.insert(entry.number, entry);
+    }
+
+    /// Adds the entries of another lexicon, replacing any with the same number.
+    pub fn extend(&mut self, other: Lexicon) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn get(&self, number: StrongsNumber) -> Option<&LexiconEntry> {
+        self.entries.get(&number)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn parse_strongs_numbers() {
+        for (text, expected) in [
+            ("G26", StrongsNumber::Greek(26)),
+            ("g0026", StrongsNumber::Greek(26)),
+            ("H7225a", StrongsNumber::Hebrew(7225)),
+            ("strong:H07225", StrongsNumber::Hebrew(7225)),
+        ] {
+            assert_eq!(text.parse(), Ok(expected));
+        }
+        for text in ["26", "G", "G0", "X26", "G26ab", "G 26"] {
+            assert!(text.parse::<StrongsNumber>().is_err(), "{}", text);
+        }
+        assert_eq!(StrongsNumber::Hebrew(430).to_string(), "H430");
+
+        assert_eq!(
+            StrongsNumber::parse_all("strong:G3588 lemma.TR:ὁ strong:G2316", 43),
+            [StrongsNumber::Greek(3588), StrongsNumber::Greek(2316)]
+        );
+        assert_eq!(
+            StrongsNumber::parse_all("H1254a,H853", 1),
+            [StrongsNumber::Hebrew(1254), StrongsNumber::Hebrew(853)]
+        );
+        assert_eq!(
+            StrongsNumber::parse_all("430", 1),
+            [StrongsNumber::Hebrew(430)]
+        );
+        assert_eq!(
+            StrongsNumber::parse_all("2316", 43),
+            [StrongsNumber::Greek(2316)]
+        );
+    }
+
+    #[test]
+    fn lexicon_lookup() {
+        let lexicon = Lexicon::embedded();
+        let agape = lexicon.get(StrongsNumber::Greek(26)).unwrap();
+        assert_eq!(agape.transliteration, "agapē");
+        assert!(agape.to_string().starts_with("G26 ἀγάπη (agapē): love"));
+        assert!(lexicon.get(StrongsNumber::Hebrew(7225)).is_some());
+
+        let mut lexicon = Lexicon::new();
+        lexicon.extend(Lexicon::from_reader("G26\tἀγάπη\tagape\tlove".as_bytes()).unwrap());
+        assert_eq!(lexicon.len(), 1);
+        assert!(Lexicon::from_reader("26\tἀγάπη\tagape\tlove".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+use crate::scripture::bible::{Bible, VerseId};
+use crate::scripture::reference::{Location, Reference};
+use std::fmt;
+use std::str::FromStr;
+
+/// How a translation numbers its chapters and verses.  Schemes are described by where they
+/// differ from the KJV, which every reference is mapped through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Versification {
+    /// The KJV numbering followed by most English translations.
+    #[default]
+    Kjv,
+    /// The Hebrew (Masoretic) numbering, where Psalm titles are verses and Malachi 4 is 3:19-24.
+    Hebrew,
+    /// The NRSV numbering, which splits 3 John 1:14 into 14-15 and moves Revelation 13:1a to 12:18.
+    Nrsv,
+    /// Byzantine text editions, which place the doxology of Romans 16:25-27 at 14:24-26.
+    Byzantine,
+}
+
+// A run of verses numbered differently from the KJV: (scheme, book, KJV chapter, first and last
+// KJV verse, chapter and first verse in the scheme).  A last verse of 255 runs to the end of the
+// chapter.  Where verses are merged or split, an unchanged rule comes first so the verse both
+// schemes share maps to itself.
+type Shift = (Versification, u8, u8, u8, u8, u8, u8);
+
+const SHIFTS: &[Shift] = &[
+    (Versification::Hebrew, 1, 31, 55, 55, 32, 1),
+    (Versification::Hebrew, 1, 32, 1, 255, 32, 2),
+    (Versification::Hebrew, 2, 8, 1, 4, 7, 26),
+    (Versification::Hebrew, 2, 8, 5, 255, 8, 1),
+    (Versification::Hebrew, 2, 22, 1, 1, 21, 37),
+    (Versification::Hebrew, 2, 22, 2, 255, 22, 1),
+    (Versification::Hebrew, 3, 6, 1, 7, 5, 20),
+    (Versification::Hebrew, 3, 6, 8, 255, 6, 1),
+    (Versification::Hebrew, 4, 16, 36, 50, 17, 1),
+    (Versification::Hebrew, 4, 17, 1, 255, 17, 16),
+    (Versification::Hebrew, 4, 29, 40, 40, 30, 1),
+    (Versification::Hebrew, 4, 30, 1, 255, 30, 2),
+    (Versification::Hebrew, 5, 12, 32, 32, 13, 1),
+    (Versification::Hebrew, 5, 13, 1, 255, 13, 2),
+    (Versification::Hebrew, 5, 22, 30, 30, 23, 1),
+    (Versification::Hebrew, 5, 23, 1, 255, 23, 2),
+    (Versification::Hebrew, 5, 29, 1, 1, 28, 69),
+    (Versification::Hebrew, 5, 29, 2, 255, 29, 1),
+    (Versification::Hebrew, 9, 23, 29, 29, 24, 1),
+    (Versification::Hebrew, 9, 24, 1, 255, 24, 2),
+    (Versification::Hebrew, 10, 18, 33, 33, 19, 1),
+    (Versification::Hebrew, 10, 19, 1, 255, 19, 2),
+    (Versification::Hebrew, 11, 4, 21, 34, 5, 1),
+    (Versification::Hebrew, 11, 5, 1, 255, 5, 15),
+    (Versification::Hebrew, 12, 11, 21, 21, 12, 1),
+    (Versification::Hebrew, 12, 12, 1, 255, 12, 2),
+    (Versification::Hebrew, 13, 6, 1, 15, 5, 27),
+    (Versification::Hebrew, 13, 6, 16, 255, 6, 1),
+    (Versification::Hebrew, 14, 2, 1, 1, 1, 18),
+    (Versification::Hebrew, 14, 2, 2, 255, 2, 1),
+    (Versification::Hebrew, 14, 14, 1, 1, 13, 23),
+    (Versification::Hebrew, 14, 14, 2, 255, 14, 1),
+    (Versification::Hebrew, 16, 4, 1, 6, 3, 33),
+    (Versification::Hebrew, 16, 4, 7, 255, 4, 1),
+    (Versification::Hebrew, 16, 9, 38, 38, 10, 1),
+    (Versification::Hebrew, 16, 10, 1, 255, 10, 2),
+    (Versification::Hebrew, 18, 41, 1, 8, 40, 25),
+    (Versification::Hebrew, 18, 41, 9, 255, 41, 1),
+    (Versification::Hebrew, 21, 5, 1, 1, 4, 17),
+    (Versification::Hebrew, 21, 5, 2, 255, 5, 1),
+    (Versification::Hebrew, 22, 6, 13, 13, 7, 1),
+    (Versification::Hebrew, 22, 7, 1, 255, 7, 2),
+    (Versification::Hebrew, 23, 9, 1, 1, 8, 23),
+    (Versification::Hebrew, 23, 9, 2, 255, 9, 1),
+    (Versification::Hebrew, 23, 64, 1, 1, 63, 19),
+    (Versification::Hebrew, 23, 64, 2, 255, 64, 1),
+    (Versification::Hebrew, 24, 9, 1, 1, 8, 23),
+    (Versification::Hebrew, 24, 9, 2, 255, 9, 1),
+    (Versification::Hebrew, 26, 20, 45, 49, 21, 1),
+    (Versification::Hebrew, 26, 21, 1, 255, 21, 6),
+    (Versification::Hebrew, 27, 4, 1, 3, 3, 31),
+    (Versification::Hebrew, 27, 4, 4, 255, 4, 1),
+    (Versification::Hebrew, 27, 5, 31, 31, 6, 1),
+    (Versification::Hebrew, 27, 6, 1, 255, 6, 2),
+    (Versification::Hebrew, 28, 1, 10, 11, 2, 1),
+    (Versification::Hebrew, 28, 2, 1, 255, 2, 3),
+    (Versification::Hebrew, 28, 11, 12, 12, 12, 1),
+    (Versification::Hebrew, 28, 12, 1, 255, 12, 2),
+    (Versification::Hebrew, 28, 13, 16, 16, 14, 1),
+    (Versification::Hebrew, 28, 14, 1, 255, 14, 2),
+    (Versification::Hebrew, 29, 2, 28, 32, 3, 1),
+    (Versification::Hebrew, 29, 3, 1, 255, 4, 1),
+    (Versification::Hebrew, 32, 1, 17, 17, 2, 1),
+    (Versification::Hebrew, 32, 2, 1, 255, 2, 2),
+    (Versification::Hebrew, 33, 5, 1, 1, 4, 14),
+    (Versification::Hebrew, 33, 5, 2, 255, 5, 1),
+    (Versification::Hebrew, 34, 1, 15, 15, 2, 1),
+    (Versification::Hebrew, 34, 2, 1, 255, 2, 2),
+    (Versification::Hebrew, 38, 1, 18, 21, 2, 1),
+    (Versification::Hebrew, 38, 2, 1, 255, 2, 5),
+    (Versification::Hebrew, 39, 4, 1, 255, 3, 19),
+    (Versification::Nrsv, 47, 13, 12, 12, 13, 12),
+    (Versification::Nrsv, 47, 13, 13, 13, 13, 12),
+    (Versification::Nrsv, 47, 13, 14, 14, 13, 13),
+    (Versification::Nrsv, 64, 1, 14, 14, 1, 14),
+    (Versification::Nrsv, 64, 1, 14, 14, 1, 15),
+    (Versification::Nrsv, 66, 13, 1, 1, 13, 1),
+    (Versification::Nrsv, 66, 13, 1, 1, 12, 18),
+    (Versification::Byzantine, 45, 16, 25, 27, 14, 24),
+];
+
+// Psalms whose titles are numbered as verse 1 in the Hebrew, with the number of verses the title
+// takes.  The KJV leaves titles unnumbered.
+const HEBREW_PSALM_TITLES: &[(u8, u8)] = &[
+    (3, 1),
+    (4, 1),
+    (5, 1),
+    (6, 1),
+    (7, 1),
+    (8, 1),
+    (9, 1),
+    (12, 1),
+    (18, 1),
+    (19, 1),
+    (20, 1),
+    (21, 1),
+    (22, 1),
+    (30, 1),
+    (31, 1),
+    (34, 1),
+    (36, 1),
+    (38, 1),
+    (39, 1),
+    (40, 1),
+    (41, 1),
+    (42, 1),
+    (44, 1),
+    (45, 1),
+    (46, 1),
+    (47, 1),
+    (48, 1),
+    (49, 1),
+    (51, 2),
+    (52, 2),
+    (53, 1),
+    (54, 2),
+    (55, 1),
+    (56, 1),
+    (57, 1),
+    (58, 1),
+    (59, 1),
+    (60, 2),
+    (61, 1),
+    (62, 1),
+    (63, 1),
+    (64, 1),
+    (65, 1),
+    (67, 1),
+    (68, 1),
+    (69, 1),
+    (70, 1),
+    (75, 1),
+    (76, 1),
+    (77, 1),
+    (80, 1),
+    (81, 1),
+    (83, 1),
+    (84, 1),
+    (85, 1),
+    (88, 1),
+    (89, 1),
+    (92, 1),
+    (102, 1),
+    (108, 1),
+    (140, 1),
+    (142, 1),
+];
+
+const PSALMS: u8 = 19;
+
+impl Versification {
+    pub const ALL: [Versification; 4] = [
+        Versification::Kjv,
+        Versification::Hebrew,
+        Versification::Nrsv,
+        Versification::Byzantine,
+    ];
+
+    /// Works out the scheme of an imported translation from the chapters and verses it has.
+    pub fn detect(bible: &Bible) -> Self {
+        let verses = |book, chapter| bible.verse_count(book, chapter).ok();
+        if bible.chapter_count(39).ok() == Some(3) || verses(PSALMS, 3) == Some(9) {
+            Versification::Hebrew
+        } else if verses(45, 14) == Some(26) {
+            Versification::Byzantine
+        } else if verses(64, 1) == Some(15) {
+            Versification::Nrsv
+        } else {
+            Versification::Kjv
+        }
+    }
+
+    /// The same verse in another scheme, e.g. Malachi 4:1 in the KJV is Malachi 3:19 in the
+    /// Hebrew.
+    pub fn convert(self, id: VerseId, to: Versification) -> VerseId {
+        to.from_kjv(self.to_kjv(id))
+    }
+
+    /// The reference with each end converted to another scheme.  A whole chapter becomes the
+    /// verses it maps to, so Malachi 4 in the KJV is Malachi 3:19 to the end of chapter 3 in the
+    /// Hebrew.
+    pub fn convert_reference(self, reference: &Reference, to: Versification) -> Reference {
+        let (start, end) = (reference.start, reference.end);
+        let first = self.convert(
+            VerseId::new(start.book, start.chapter, start.verse.unwrap_or(1)),
+            to,
+        );
+        let first_verse = match start.verse {
+            None if first.verse() == 1 => None,
+            _ => Some(first.verse()),
+        };
+        let last = self.convert(
+            VerseId::new(end.book, end.chapter, end.verse.unwrap_or(u8::MAX)),
+            to,
+        );
+        Reference::new(
+            Location::new(first.book(), first.chapter(), first_verse),
+            Location::new(last.book(), last.chapter(), end.verse.map(|_| last.verse())),
+        )
+    }
+
+    pub fn to_kjv(self, id: VerseId) -> VerseId {
+        if self == Versification::Kjv {
+            return id;
+        }
+        if let Some(title) = self.psalm_title(id) {
+            return VerseId::new(
+                PSALMS,
+                id.chapter(),
+                id.verse().saturating_sub(title).max(1),
+            );
+        }
+        self.shifts()
+            .find(|&&(_, book, _, first, last, chapter, to_first)| {
+                let last = to_first as u16 + (last - first) as u16;
+                id.book() == book
+                    && id.chapter() == chapter
+                    && (to_first as u16..=last).contains(&(id.verse() as u16))
+            })
+            .map_or(id, |&(_, book, kjv_chapter, first, _, _, to_first)| {
+                VerseId::new(
+                    book,
+                    kjv_chapter,
+                    first.saturating_add(id.verse() - to_first),
+                )
+            })
+    }
+
+    pub fn from_kjv(self, id: VerseId) -> VerseId {
+        if self == Versification::Kjv {
+            return id;
+        }
+        if let Some(title) = self.psalm_title(id) {
+            return VerseId::new(PSALMS, id.chapter(), id.verse().saturating_add(title));
+        }
+        self.shifts()
+            .find(|&&(_, book, kjv_chapter, first, last, _, _)| {
+                id.book() == book
+                    && id.chapter() == kjv_chapter
+                    && (first..=last).contains(&id.verse())
+            })
+            .map_or(id, |&(_, book, _, first, _, chapter, to_first)| {
+                VerseId::new(book, chapter, to_first.saturating_add(id.verse() - first))
+            })
+    }
+
+    fn shifts(self) -> impl Iterator<Item = &'static Shift> {
+        SHIFTS.iter().filter(move |shift| shift.0 == self)
+    }
+
+    // The number of verses a psalm's title takes in this scheme, for psalms with numbered titles.
+    fn psalm_title(self, id: VerseId) -> Option<u8> {
+        if self != Versification::Hebrew || id.book() != PSALMS {
+            return None;
+        }
+        HEBREW_PSALM_TITLES
+            .iter()
+            .find(|(psalm, _)| *psalm == id.chapter())
+            .map(|(_, title)| *title)
+    }
+}
+
+impl fmt::Display for Versification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Versification::Kjv => "KJV",
+            Versification::Hebrew => "Hebrew",
+            Versification::Nrsv => "NRSV",
+            Versification::Byzantine => "Byzantine",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Versification {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Versification::ALL
+            .into_iter()
+            .find(|scheme| scheme.to_string().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("{} is not a known versification", name))
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    fn convert(from: Versification, to: Versification, (b, c, v): (u8, u8, u8)) -> (u8, u8, u8) {
+        let id = from.convert(VerseId::new(b, c, v), to);
+        (id.book(), id.chapter(), id.verse())
+    }
+
+    #[test]
+    fn converts_between_schemes() {
+        use Versification::*;
+        assert_eq!(convert(Kjv, Hebrew, (39, 4, 6)), (39, 3, 24));
+        assert_eq!(convert(Hebrew, Kjv, (39, 3, 19)), (39, 4, 1));
+        assert_eq!(convert(Hebrew, Kjv, (39, 3, 18)), (39, 3, 18));
+        assert_eq!(convert(Kjv, Hebrew, (19, 51, 1)), (19, 51, 3));
+        assert_eq!(convert(Hebrew, Kjv, (19, 3, 1)), (19, 3, 1));
+        assert_eq!(convert(Hebrew, Kjv, (19, 3, 9)), (19, 3, 8));
+        assert_eq!(convert(Kjv, Hebrew, (19, 23, 1)), (19, 23, 1));
+        assert_eq!(convert(Kjv, Hebrew, (29, 2, 28)), (29, 3, 1));
+        assert_eq!(convert(Kjv, Hebrew, (29, 3, 21)), (29, 4, 21));
+        assert_eq!(convert(Nrsv, Kjv, (64, 1, 15)), (64, 1, 14));
+        assert_eq!(convert(Kjv, Nrsv, (64, 1, 14)), (64, 1, 14));
+        assert_eq!(convert(Nrsv, Kjv, (47, 13, 12)), (47, 13, 12));
+        assert_eq!(convert(Kjv, Nrsv, (47, 13, 14)), (47, 13, 13));
+        assert_eq!(convert(Kjv, Byzantine, (45, 16, 26)), (45, 14, 25));
+        assert_eq!(convert(Hebrew, Byzantine, (39, 3, 20)), (39, 4, 2));
+    }
+
+    #[test]
+    fn converts_references() {
+        let reference: Reference = "Mal 4".parse().unwrap();
+        let converted = Versification::Kjv.convert_reference(&reference, Versification::Hebrew);
+        assert_eq!(converted.start, Location::new(39, 3, Some(19)));
+        assert_eq!(converted.end, Location::new(39, 3, None));
+        assert_eq!(
+            Versification::Kjv
+                .convert_reference(&"Mal 4:1-6".parse().unwrap(), Versification::Hebrew)
+                .to_string(),
+            "Malachi 3:19-24"
+        );
+        assert_eq!("hebrew".parse(), Ok(Versification::Hebrew));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::clean_text;
+use crate::scripture::bible::{Bible, Verse};
+use crate::scripture::markup::escape;
+use crate::scripture::reference::usfm_book_id;
+use crate::scripture::strongs::{StrongsNumber, TaggedWord};
+use regex::Regex;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+// Markers whose content is left out of the verse text: footnotes, cross references, alternate
+// verse and chapter numbers and figures.  Each is closed by the same marker with a *.
+// Footnotes are kept as markup.
+const NOTE_MARKERS: [&str; 10] = ["f", "fe", "ef", "x", "ex", "va", "vp", "ca", "fig", "rq"];
+
+// Footnote markers whose text is not part of the note: the caller, as in \f + , and the
+// reference and verse number it is for.
+const FOOTNOTE_SKIPPED_MARKERS: [&str; 4] = ["f", "fe", "fr", "fv"];
+
+// Markers of section headings, Psalm titles and acrostic headings, whose line is kept as a
+// heading of the verse after it.
+const SECTION_MARKERS: [&str; 4] = ["s", "ms", "d", "qa"];
+
+// Markers that start a title, heading or introduction line rather than verse text.
+const HEADING_MARKERS: [&str; 21] = [
+    "h", "toc", "toca", "mt", "mte", "ms", "mr", "s", "sr", "r", "d", "sp", "sd", "cl", "cp", "cd",
+    "rem", "ide", "sts", "usfm", "periph",
+];
+
+/// Imports a USFM file, or a directory of them with one book per file as most translations
+/// are distributed.
+pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
+    let path = Path::new(bible_import_path);
+    let mut verses = Vec::new();
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            let extension = path
+                .extension()
+                .and_then(|s| s.to_str())
+                .map(str::to_lowercase);
+            if matches!(extension.as_deref(), Some("usfm" | "sfm")) {
+                verses.extend(read_verses(&fs::read_to_string(&path)?));
+            }
+        }
+    } else {
+        verses = read_verses(&fs::read_to_string(path)?);
+    }
+
+    if verses.is_empty() {
+        return Err(format!("No USFM verses found in {}", bible_import_path).into());
+    }
+    Ok(bible_from_verses(verses))
+}
+
+pub fn bible_from_str(usfm: &str) -> Bible {
+    bible_from_verses(read_verses(usfm))
+}
+
+// Book files are read in whatever order the directory lists them, so put the books in canonical
+// order.  The sort is stable, keeping each book's chapters and verses in the order written.
+fn bible_from_verses(mut verses: Vec<Verse>) -> Bible {
+    verses.sort_by_key(|verse| verse.book);
+    let mut bible = Bible::new();
+    for verse in verses {
+        bible.insert(verse);
+    }
+    bible
+}
+
+fn marker_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\\\+?([a-z]+)(\d*)(\*?)").expect("Invalid regex pattern"))
+}
+
+// The Strong's numbers among USFM 3 word attributes, as in \w grace|strong="G5485"\w*.
+fn strong_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r#"strong="([^"]*)""#).expect("Invalid regex pattern"))
+}
+
+// A verse number with its text, the words tagged with Strong's numbers and its headings.
+type VerseInProgress = (u8, String, Vec<TaggedWord>, Vec<String>);
+
+// The text is collected as markup, with words of Jesus, \wj, supplied words, \add, and footnotes
+// marked.  Headings belong to the verse after them, or to the verse they are in before its words.
+fn read_verses(usfm: &str) -> Vec<Verse> {
+    let markers: Vec<_> = marker_regex().captures_iter(usfm).collect();
+    let mut verses = Vec::new();
+    let (mut book, mut chapter) = (None, 0);
+    let mut current: Option<VerseInProgress> = None;
+    let mut note_depth: usize = 0;
+    let mut footnote: Option<String> = None;
+    // Whether the words of Jesus go on into the next verse.
+    let mut words_of_jesus = false;
+    let mut pending_headings: Vec<String> = Vec::new();
+
+    let mut finish_verse = |book: Option<u8>, chapter: u8, current: Option<VerseInProgress>| {
+        if let (Some(book), Some((verse, text, words, headings))) = (book, current) {
+            let mut verse = Verse::new(book, chapter, verse, text).with_markup();
+            if !verse.scripture.is_empty() {
+                verse.words = words;
+                verse.headings = headings;
+                verses.push(verse);
+            }
+        }
+    };
+
+    for (i, captures) in markers.iter().enumerate() {
+        let marker_end = captures.get(0).map_or(0, |m| m.end());
+        let text_end = markers
+            .get(i + 1)
+            .and_then(|next| next.get(0))
+            .map_or(usfm.len(), |m| m.start());
+        // Word attributes such as the |strong="G5485" of \w grace|strong="G5485"\w* are not
+        // part of the text.
+        let (text, attributes) = usfm[marker_end..text_end]
+            .split_once('|')
+            .unwrap_or((&usfm[marker_end..text_end], ""));
+        let marker = &captures[1];
+        let closing = !captures[3].is_empty();
+
+        // Text is only kept when it follows a marker outside of a footnote or heading.
+        let kept = match marker {
+            "id" => {
+                finish_verse(book, chapter, current.take());
+                book = text.split_whitespace().next().and_then(usfm_book_id);
+                chapter = 0;
+                words_of_jesus = false;
+                pending_headings.clear();
+                ""
+            }
+            "c" => {
+                finish_verse(book, chapter, current.take());
+                chapter = first_number(text).unwrap_or(0);
+                ""
+            }
+            "v" => {
+                finish_verse(book, chapter, current.take());
+                // Bridged verses such as \v 1-2 are filed under the first.
+                current = first_number(text).map(|verse| {
+                    let text = if words_of_jesus { "‹" } else { "" };
+                    let headings = std::mem::take(&mut pending_headings);
+                    (verse, text.to_string(), Vec::new(), headings)
+                });
+                text.trim_start()
+                    .split_once(char::is_whitespace)
+                    .map_or("", |(_, rest)| rest)
+            }
+            _ if NOTE_MARKERS.contains(&marker) => {
+                if closing {
+                    note_depth = note_depth.saturating_sub(1);
+                    if let (0, Some(note)) = (note_depth, footnote.take()) {
+                        if let Some((_, verse_text, ..)) = current.as_mut() {
+                            verse_text.push_str(&format!("{{{}}}", escape(&note)));
+                        }
+                    }
+                    text
+                } else {
+                    if note_depth == 0 && matches!(marker, "f" | "fe") {
+                        footnote = Some(String::new());
+                    }
+                    note_depth += 1;
+                    ""
+                }
+            }
+            _ if SECTION_MARKERS.contains(&marker) && note_depth == 0 => {
+                let (line, rest) = text.split_once('\n').unwrap_or((text, ""));
+                let heading = clean_text(line);
+                match current.as_mut() {
+                    _ if heading.is_empty() => (),
+                    Some((_, verse_text, _, headings))
+                        if !verse_text.chars().any(char::is_alphanumeric) =>
+                    {
+                        headings.push(heading)
+                    }
+                    _ => pending_headings.push(heading),
+                }
+                rest
+            }
+            _ if HEADING_MARKERS.contains(&marker)
+                || (marker.starts_with('i') && marker != "it") =>
+            {
+                text.split_once('\n').map_or("", |(_, rest)| rest)
+            }
+            _ => text,
+        };
+
+        let mark = match (marker, closing) {
+            ("wj", false) => "‹",
+            ("wj", true) => "›",
+            ("add", false) => "[",
+            ("add", true) => "]",
+            _ => "",
+        };
+        if marker == "wj" && note_depth == 0 {
+            words_of_jesus = !closing;
+        }
+
+        if note_depth == 1 && !FOOTNOTE_SKIPPED_MARKERS.contains(&marker) {
+            if let Some(note) = footnote.as_mut() {
+                note.push_str(text);
+            }
+        }
+        if note_depth == 0 {
+            if let (Some((_, verse_text, words, _)), Some(book)) = (current.as_mut(), book) {
+                verse_text.push_str(mark);
+                verse_text.push_str(&escape(kept));
+                let strong = strong_regex().captures(attributes);
+                if let (Some(strong), "w", false) = (strong, marker, closing) {
+                    let strongs = StrongsNumber::parse_all(&strong[1], book);
+                    if !strongs.is_empty() {
+                        words.push(TaggedWord {
+                            text: clean_text(kept),
+                            strongs,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    finish_verse(book, chapter, current);
+    verses
+}
+
+// The number at the start of text such as "16 For God" or "1-2 In the beginning".
+fn first_number(text: &str) -> Option<u8> {
+    let digits: String = text
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+    use crate::scripture::markup::TextStyle;
+
+    #[test]
+    fn imports_verses_without_notes_or_headings() {
+        let bible = bible_from_str(
+            r#"\id REV World English Bible
+\c 22
+\p
+\v 21 The grace of the Lord Jesus Christ be with all the saints. Amen.
+\id JHN World English Bible
+\h John
+\toc1 The Good News According to John
+\mt1 The Good News According to John
+\c 3
+\s1 Jesus and Nicodemus
+\p
+\v 16 \wj For \+w God|strong="G2316"\+w* so loved the world,\f + \fr 3:16 \ft Or, only born\f* that he gave his \w one|strong="G1520"\w* and only Son,\wj*
+\v 17 \wj For God didn’t send his Son into the world\x - \xo 3:17 \xt Luke 19:10\x* to judge the world\wj*
+\q1 but that the world should be saved through him.
+\v 18-19 \va 18\va* Bridged \add verses\add*.
+"#,
+        );
+
+        assert_eq!(bible.len(), 4);
+        let verses = bible.lookup("John 3:16-19").unwrap();
+        assert_eq!(verses[0].reference, "John 3:16");
+        assert_eq!(
+            verses[0].scripture,
+            "For God so loved the world, that he gave his one and only Son,"
+        );
+        assert_eq!(
+            verses[1].scripture,
+            "For God didn’t send his Son into the world to judge the world but that the world should be saved through him."
+        );
+        assert_eq!(
+            verses[0].words,
+            [
+                TaggedWord {
+                    text: "God".to_string(),
+                    strongs: vec![StrongsNumber::Greek(2316)]
+                },
+                TaggedWord {
+                    text: "one".to_string(),
+                    strongs: vec![StrongsNumber::Greek(1520)]
+                }
+            ]
+        );
+        assert_eq!(verses[2].verse, 18);
+        assert_eq!(verses[2].scripture, "Bridged verses.");
+        assert_eq!(
+            verses[0].render(TextStyle::Markup),
+            "‹For God so loved the world,{Or, only born} that he gave his one and only Son,›"
+        );
+        assert_eq!(verses[1].spans.len(), 1);
+        assert_eq!(verses[0].headings, ["Jesus and Nicodemus"]);
+        assert!(verses[1].headings.is_empty());
+        assert_eq!(verses[2].render(TextStyle::Brackets), "Bridged [verses].");
+        // Revelation comes first in the file but is stored after John.
+        assert_eq!(
+            bible.get_next_scripture("John 3:18", 1)[0].reference,
+            "Revelation 22:21"
+        );
+    }
+
+    #[test]
+    fn imports_headings() {
+        let bible = bible_from_str(
+            "\\id PSA\n\\c 3\n\\d A Psalm by David.\n\\q1\n\\v 1 Yahweh, how my adversaries have increased!\n\\c 119\n\\qa ALEPH\n\\v 1 Blessed are those.\n\\v 2 \\d In some texts.\n\\q1 Blessed are those who keep.\n",
+        );
+
+        let verses = bible.lookup("Psalm 3:1").unwrap();
+        assert_eq!(verses[0].headings, ["A Psalm by David."]);
+        let verses = bible.lookup("Psalm 119:1-2").unwrap();
+        assert_eq!(verses[0].headings, ["ALEPH"]);
+        assert_eq!(verses[0].scripture, "Blessed are those.");
+        assert_eq!(verses[1].headings, ["In some texts."]);
+        assert_eq!(verses[1].scripture, "Blessed are those who keep.");
+    }
+}
@@ -0,0 +1,260 @@
+use crate::scripture::bible::{Bible, VerseId};
+use crate::scripture::books::{Book, Canon};
+use crate::scripture::reference::book_name;
+use crate::scripture::versification::Versification;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// A problem found in an imported translation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The same verse is in the translation more than once.
+    Duplicate(VerseId),
+    /// A verse comes after one that should follow it.
+    OutOfOrder {
+        verse: VerseId,
+        after: VerseId,
+    },
+    /// The reference text of a row names another verse than its book, chapter and verse numbers.
+    ReferenceMismatch {
+        verse: VerseId,
+        reference: String,
+    },
+    /// A verse past the end of its chapter or book, or in a book outside the translation's canon.
+    Unexpected(VerseId),
+    /// A run of verses in the canon that the translation leaves out, in KJV numbering.
+    Missing {
+        first: VerseId,
+        last: VerseId,
+    },
+    EmptyText(VerseId),
+    /// Markup left over from converting the translation, such as <i> or &amp;.
+    Html(VerseId),
+    /// Text that was decoded with the wrong encoding, such as â€™ for ’.
+    Encoding(VerseId),
+}
+
+/// The result of checking a translation against the canon.
+#[derive(Debug)]
+pub struct ValidationReport {
+    pub verses: usize,
+    pub versification: Versification,
+    pub canon: Canon,
+    /// Books of its canon the translation does not include at all, which are not reported verse by verse.
+    pub missing_books: Vec<u8>,
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Checks every verse of an imported translation, in the order it was imported, for duplicate,
+/// misordered, missing and unexpected verses and for suspicious text.  Only the books of the
+/// translation's canon, as inferred from the books it has, are expected.
+pub fn validate(bible: &Bible) -> ValidationReport {
+    validate_against(bible, bible.canon())
+}
+
+/// Like `validate`, but expects the books of the given canon.
+pub fn validate_against(bible: &Bible, canon: Canon) -> ValidationReport {
+    let versification = bible.versification();
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    let mut present = HashSet::new();
+    let mut previous: Option<VerseId> = None;
+
+    for verse in bible.verses() {
+        let id = verse.id();
+        if !seen.insert(id) {
+            issues.push(Issue::Duplicate(id));
+        } else if let Some(after) = previous.filter(|previous| id < *previous) {
+            issues.push(Issue::OutOfOrder { verse: id, after });
+        }
+        previous = Some(id);
+
+        if VerseId::from_reference(&verse.reference) != Some(id) {
+            issues.push(Issue::ReferenceMismatch {
+                verse: id,
+                reference: verse.reference.clone(),
+            });
+        }
+
+        let kjv = versification.to_kjv(id);
+        match Book::get(kjv.book()).filter(|book| canon.contains(book.id)) {
+            Some(book) if book.check_verse(kjv.chapter(), kjv.verse()).is_ok() => {
+                present.insert(kjv);
+            }
+            _ => issues.push(Issue::Unexpected(id)),
+        }
+
+        let text = verse.scripture.trim();
+        if text.is_empty() {
+            issues.push(Issue::EmptyText(id));
+        } else if markup_regex().is_match(text) {
+            issues.push(Issue::Html(id));
+        } else if mojibake_regex().is_match(text) || text.contains('\u{FFFD}') {
+            issues.push(Issue::Encoding(id));
+        }
+    }
+
+    let books: HashSet<u8> = present.iter().map(|id| id.book()).collect();
+    let mut missing_books = Vec::new();
+    for book in canon.books() {
+        if !books.contains(&book.id) {
+            missing_books.push(book.id);
+            continue;
+        }
+        for (chapter, verses) in (1..).zip(book.verses) {
+            let mut run: Option<(VerseId, VerseId)> = None;
+            for verse in 1..=*verses {
+                let id = VerseId::new(book.id, chapter, verse);
+                if present.contains(&id) {
+                    if let Some((first, last)) = run.take() {
+                        issues.push(Issue::Missing { first, last });
+                    }
+                } else {
+                    run = Some((run.map_or(id, |(first, _)| first), id));
+                }
+            }
+            if let Some((first, last)) = run {
+                issues.push(Issue::Missing { first, last });
+            }
+        }
+    }
+
+    ValidationReport {
+        verses: bible.len(),
+        versification,
+        canon,
+        missing_books,
+        issues,
+    }
+}
+
+fn markup_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"</?[a-zA-Z][^<>]*>|&(#\d+|#x[0-9a-fA-F]+|[a-zA-Z]+);")
+            .expect("Invalid regex pattern")
+    })
+}
+
+// UTF-8 read as Windows-1252 or Latin-1, e.g. â€œ for “ and Ã© for é.
+fn mojibake_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"â€|[ÃÂ][\u{A0}-\u{BF}]").expect("Invalid regex pattern"))
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::Duplicate(verse) => write!(f, "{} appears more than once", verse),
+            Issue::OutOfOrder { verse, after } => write!(f, "{} comes after {}", verse, after),
+            Issue::ReferenceMismatch { verse, reference } => {
+                write!(f, "{} is labelled \"{}\"", verse, reference)
+            }
+            Issue::Unexpected(verse) => write!(f, "{} is not in the canon", verse),
+            Issue::Missing { first, last } if first == last => write!(f, "{} is missing", first),
+            Issue::Missing { first, last } => write!(f, "{}-{} are missing", first, last.verse()),
+            Issue::EmptyText(verse) => write!(f, "{} has no text", verse),
+            Issue::Html(verse) => write!(f, "{} contains markup", verse),
+            Issue::Encoding(verse) => write!(f, "{} contains badly encoded characters", verse),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} verses, {} versification, {} canon, {} issues",
+            self.verses,
+            self.versification,
+            self.canon,
+            self.issues.len()
+        )?;
+        if !self.missing_books.is_empty() {
+            let books: Vec<&str> = self
+                .missing_books
+                .iter()
+                .map(|book| book_name(*book))
+                .collect();
+            write!(f, "\nBooks not included: {}", books.join(", "))?;
+        }
+        for issue in &self.issues {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+    use crate::scripture::bible::Verse;
+
+    #[test]
+    fn reports_problems() {
+        let mut bible = Bible::new();
+        for verse in 1..=25 {
+            let text = match verse {
+                2 => "",
+                3 => "Grace <i>to</i> you",
+                4 => "I thank my God, making mention of thee alwaysâ€™",
+                _ => "Paul, a prisoner of Jesus Christ",
+            };
+            bible.insert(Verse::new(57, 1, verse, text.to_string()));
+        }
+        let mut relabelled = Verse::new(57, 1, 26, "Extra".to_string());
+        relabelled.reference = "Philemon 1:62".to_string();
+        bible.insert(relabelled);
+        bible.insert(Verse::new(57, 1, 5, "Again".to_string()));
+
+        let mut report = validate(&bible);
+        assert_eq!(report.missing_books.len(), 65);
+        assert!(report
+            .to_string()
+            .starts_with("27 verses, KJV versification, Protestant canon, 6 issues"));
+
+        report = validate_against(&bible, Canon::Catholic);
+        assert_eq!(report.missing_books.len(), 76);
+        let issues: Vec<String> = report.issues.iter().map(Issue::to_string).collect();
+        assert_eq!(
+            issues,
+            [
+                "Philemon 1:2 has no text",
+                "Philemon 1:3 contains markup",
+                "Philemon 1:4 contains badly encoded characters",
+                "Philemon 1:26 is labelled \"Philemon 1:62\"",
+                "Philemon 1:26 is not in the canon",
+                "Philemon 1:5 appears more than once",
+            ]
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn reports_missing_and_misordered_verses() {
+        let mut bible = Bible::new();
+        for verse in [1, 2, 6, 7, 4, 10, 11, 12, 13] {
+            bible.insert(Verse::new(63, 1, verse, "Text".to_string()));
+        }
+
+        let report = validate(&bible);
+        let issues: Vec<String> = report.issues.iter().map(Issue::to_string).collect();
+        assert_eq!(
+            issues,
+            [
+                "2 John 1:4 comes after 2 John 1:7",
+                "2 John 1:3 is missing",
+                "2 John 1:5 is missing",
+                "2 John 1:8-9 are missing",
+            ]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::clean_text;
+use crate::scripture::bible::{Bible, Verse};
+use crate::scripture::books::{Book, Canon};
+use crate::scripture::markup::escape;
+use crate::scripture::strongs::{StrongsNumber, TaggedWord};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::error::Error;
+use std::io::BufRead;
+use std::mem;
+
+// Elements inside a verse whose text is not part of it, such as cross references and Strong's
+// glosses.  Notes are kept as footnotes, but notes within them are skipped.
+const SKIPPED_ELEMENTS: [&[u8]; 3] = [b"NOTE", b"REMARK", b"XREF"];
+
+pub fn bible_import(bible_import_path: &str) -> Result<Bible, Box<dyn Error>> {
+    read_bible(Reader::from_file(bible_import_path)?)
+}
+
+pub fn bible_from_str(xml: &str) -> Result<Bible, Box<dyn Error>> {
+    read_bible(Reader::from_str(xml))
+}
+
+// Zefania nests verses as <BIBLEBOOK bnumber="43"><CHAPTER cnumber="3"><VERS vnumber="16">.
+// The text is collected as markup, with italic words as supplied, red words as the words of
+// Jesus and notes as footnotes.  Captions, <CAPTION>A Psalm of David.</CAPTION>, are headings of
+// the verse after them.
+fn read_bible<R: BufRead>(mut reader: Reader<R>) -> Result<Bible, Box<dyn Error>> {
+    let mut bible = Bible::new();
+    let mut buffer = Vec::new();
+    let (mut book, mut chapter) = (0, 0);
+    let mut current: Option<(u8, String)> = None;
+    // Words tagged with Strong's numbers, as in <gr str="2316">God</gr>.
+    let mut words: Vec<TaggedWord> = Vec::new();
+    let mut word: Option<TaggedWord> = None;
+    // The close marks of the <STYLE> elements open, if they are markup.
+    let mut styles: Vec<Option<char>> = Vec::new();
+    let mut note: Option<String> = None;
+    // The headings of the current verse and those waiting for the next.
+    let mut headings: Vec<String> = Vec::new();
+    let mut pending_headings: Vec<String> = Vec::new();
+    let mut caption: Option<String> = None;
+    let mut skip_depth: usize = 0;
+
+    loop {
+        match reader.read_event_into(&mut buffer)? {
+            Event::Eof => break,
+            Event::Start(element)
+                if element.local_name().as_ref() == b"NOTE"
+                    && skip_depth == 0
+                    && note.is_none()
+                    && current.is_some() =>
+            {
+                note = Some(String::new());
+            }
+            Event::End(element) if element.local_name().as_ref() == b"NOTE" && skip_depth == 0 => {
+                if let (Some(note), Some((_, text))) = (note.take(), current.as_mut()) {
+                    text.push_str(&format!("{{{}}}", escape(&note)));
+                }
+            }
+            Event::Start(element) if SKIPPED_ELEMENTS.contains(&element.local_name().as_ref()) => {
+                skip_depth += 1;
+            }
+            Event::End(element) if SKIPPED_ELEMENTS.contains(&element.local_name().as_ref()) => {
+                skip_depth = skip_depth.saturating_sub(1);
+            }
+            _ if skip_depth > 0 => (),
+            Event::Text(text) if note.is_some() => {
+                if let Some(note) = note.as_mut() {
+                    note.push_str(&text.unescape()?);
+                }
+            }
+            _ if note.is_some() => (),
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"BIBLEBOOK" => {
+                    book = book_number(&element)?;
+                    pending_headings.clear();
+                }
+                b"CHAPTER" => chapter = number(&element, "cnumber")?,
+                b"VERS" => {
+                    current = Some((number(&element, "vnumber")?, String::new()));
+                    words.clear();
+                    headings = mem::take(&mut pending_headings);
+                }
+                b"CAPTION" => caption = Some(String::new()),
+                b"gr" if current.is_some() => word = tagged_word(&element, book)?,
+                b"STYLE" => {
+                    let marks = style_marks(&element)?;
+                    if let (Some((open, _)), Some((_, text))) = (marks, current.as_mut()) {
+                        text.push(open);
+                    }
+                    styles.push(marks.map(|(_, close)| close));
+                }
+                _ => (),
+            },
+            Event::End(element) if element.local_name().as_ref() == b"CAPTION" => {
+                let heading = clean_text(&caption.take().unwrap_or_default());
+                if !heading.is_empty() {
+                    pending_headings.push(heading);
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"STYLE" => {
+                if let (Some(Some(close)), Some((_, text))) = (styles.pop(), current.as_mut()) {
+                    text.push(close);
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"gr" => {
+                if let Some(mut tagged) = word.take() {
+                    tagged.text = clean_text(&tagged.text);
+                    if !tagged.text.is_empty() {
+                        words.push(tagged);
+                    }
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"VERS" => {
+                if let Some((verse, text)) = current.take() {
+                    let mut verse = Verse::new(book, chapter, verse, text).with_markup();
+                    if book != 0 && !verse.scripture.is_empty() {
+                        verse.words = mem::take(&mut words);
+                        verse.headings = mem::take(&mut headings);
+                        bible.insert(verse);
+                    }
+                }
+            }
+            Event::Empty(element) if element.local_name().as_ref() == b"BR" => {
+                if let Some((_, text)) = current.as_mut() {
+                    text.push(' ');
+                }
+            }
+            Event::Text(text) if caption.is_some() => {
+                if let Some(caption) = caption.as_mut() {
+                    caption.push_str(&text.unescape()?);
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, verse_text)) = current.as_mut() {
+                    let text = text.unescape()?;
+                    verse_text.push_str(&escape(&text));
+                    if let Some(tagged) = word.as_mut() {
+                        tagged.text.push_str(&text);
+                    }
+                }
+            }
+            _ => (),
+        }
+        buffer.clear();
+    }
+    Ok(bible)
+}
+
+// Zefania files agree on the numbers of the 66 books but not of the deuterocanon, so books past
+// Revelation are found by name.  Books that are not in the registry are 0 and skipped.
+fn book_number(element: &BytesStart) -> Result<u8, Box<dyn Error>> {
+    let book = number(element, "bnumber")?;
+    if Canon::Protestant.contains(book) {
+        return Ok(book);
+    }
+    let Some(name) = element.try_get_attribute("bname")? else {
+        return Ok(0);
+    };
+    Ok(Book::find(&name.unescape_value()?)
+        .filter(|book| book.is_deuterocanonical())
+        .map_or(0, |book| book.id))
+}
+
+// The marks of a <STYLE> element for supplied words, written in italic as in
+// <STYLE fs="italic"> or <STYLE css="font-style:italic">, or for the words of Jesus, written in
+// red as in <STYLE css="color:#ff0000">.  None for other styles.
+fn style_marks(element: &BytesStart) -> Result<Option<(char, char)>, Box<dyn Error>> {
+    let mut style = String::new();
+    for name in ["fs", "css"] {
+        if let Some(value) = element.try_get_attribute(name)? {
+            style.push_str(&value.unescape_value()?.to_lowercase().replace(' ', ""));
+            style.push(';');
+        }
+    }
+    Ok(if style.contains("italic") {
+        Some(('[', ']'))
+    } else if ["color:red", "color:#ff0000", "color:#f00;"]
+        .iter()
+        .any(|red| style.contains(red))
+    {
+        Some(('‹', '›'))
+    } else {
+        None
+    })
+}
+
+// A <gr> element with Strong's numbers, ready for its text.
+fn tagged_word(element: &BytesStart, book: u8) -> Result<Option<TaggedWord>, Box<dyn Error>> {
+    let Some(numbers) = element.try_get_attribute("str")? else {
+        return Ok(None);
+    };
+    let strongs = StrongsNumber::parse_all(&numbers.unescape_value()?, book);
+    Ok((!strongs.is_empty()).then(|| TaggedWord {
+        text: String::new(),
+        strongs,
+    }))
+}
+
+fn number(element: &BytesStart, attribute: &str) -> Result<u8, Box<dyn Error>> {
+    let value = element
+        .try_get_attribute(attribute)?
+        .ok_or_else(|| format!("Missing {} attribute", attribute))?
+        .unescape_value()?;
+    Ok(value.trim().parse()?)
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+    use crate::scripture::markup::{Markup, TextStyle};
+
+    #[test]
+    fn imports_verses() {
+        let bible = bible_from_str(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <XMLBIBLE biblename="King James Version">
+              <BIBLEBOOK bnumber="19" bname="Psalms">
+                <CHAPTER cnumber="23">
+                  <CAPTION>A Psalm of David.</CAPTION>
+                  <VERS vnumber="1">The <gr str="3068">LORD</gr> <STYLE css="font-style:italic">is</STYLE> my <gr str="7462">shepherd</gr>;<BR/>I shall not want.</VERS>
+                  <VERS vnumber="2">He maketh me to lie down<NOTE type="x-studynote">Heb. pastures of tender grass</NOTE> in green pastures.</VERS>
+                </CHAPTER>
+              </BIBLEBOOK>
+              <BIBLEBOOK bnumber="69" bname="Tobit">
+                <CHAPTER cnumber="1"><VERS vnumber="1">The book of the <STYLE css="color: #FF0000">words</STYLE> of Tobit.</VERS></CHAPTER>
+              </BIBLEBOOK>
+              <BIBLEBOOK bnumber="88" bname="Enoch">
+                <CHAPTER cnumber="1"><VERS vnumber="1">Not a known book.</VERS></CHAPTER>
+              </BIBLEBOOK>
+            </XMLBIBLE>"#,
+        )
+        .unwrap();
+
+        assert_eq!(bible.len(), 3);
+        assert_eq!(bible.lookup("Tob 1:1").unwrap()[0].reference, "Tobit 1:1");
+        let verses = bible.lookup("Psalm 23").unwrap();
+        assert_eq!(verses[0].reference, "Psalm 23:1");
+        assert_eq!(
+            verses[0].scripture,
+            "The LORD is my shepherd; I shall not want."
+        );
+        assert_eq!(
+            verses[1].scripture,
+            "He maketh me to lie down in green pastures."
+        );
+        assert_eq!(
+            verses[0].find_words("shepherd")[0].strongs,
+            [StrongsNumber::Hebrew(7462)]
+        );
+        assert!(verses[1].words.is_empty());
+        assert_eq!(
+            verses[0].render(TextStyle::Brackets),
+            "The LORD [is] my shepherd; I shall not want."
+        );
+        assert_eq!(
+            verses[1].render(TextStyle::Markup),
+            "He maketh me to lie down{Heb. pastures of tender grass} in green pastures."
+        );
+        let tobit = &bible.lookup("Tob 1:1").unwrap()[0];
+        assert_eq!(tobit.scripture, "The book of the words of Tobit.");
+        assert_eq!(tobit.spans[0].markup, Markup::WordsOfJesus);
+        assert_eq!(verses[0].headings, ["A Psalm of David."]);
+        assert!(verses[1].headings.is_empty());
+    }
+}
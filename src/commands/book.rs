@@ -0,0 +1,64 @@
+use bible::scripture::books::{Book, Canon};
+use bible::scripture::reference::ReferenceError;
+
+pub async fn book(params: Vec<String>) -> Option<String> {
+    let help_message = "Book Help: Shows which testament a book is in and how many chapters and verses it has, or the verses in one of its chapters. Usage: !book romans | !book 1 john | !book psalm 119";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    // A trailing number is a chapter, unless it is all there is, as in the 1 of "!book 1 john".
+    let chapter = match params.split_last() {
+        Some((last, rest)) if !rest.is_empty() => last.parse::<u8>().ok(),
+        _ => None,
+    };
+    let name_words = match chapter {
+        Some(_) => &params[..params.len() - 1],
+        None => &params[..],
+    };
+    let name = name_words.join(" ");
+    let Some(book) = Book::find(&name) else {
+        return Some(ReferenceError::UnknownBook(name).to_string());
+    };
+
+    match chapter {
+        Some(chapter) => Some(match book.verses_in(chapter) {
+            Some(verses) => format!("{} {} has {} verses.", book.name, chapter, verses),
+            None => book
+                .check_verse(chapter, 1)
+                .err()
+                .map(|e| e.to_string())
+                .unwrap_or_default(),
+        }),
+        None => {
+            let chapters = match book.chapters() {
+                1 => "1 chapter".to_string(),
+                chapters => format!("{} chapters", chapters),
+            };
+            let position = if book.is_deuterocanonical() {
+                format!(
+                    "a deuterocanonical book of the {}, in Catholic and Orthodox Bibles",
+                    book.testament
+                )
+            } else {
+                format!(
+                    "book {} of {} in the {}",
+                    book.id,
+                    Canon::Protestant.books().count(),
+                    book.testament
+                )
+            };
+            Some(format!(
+                "{} is {}, with {} and {} verses. Also written: {}",
+                book.name,
+                position,
+                chapters,
+                book.verse_count(),
+                book.names[1..].join(", ")
+            ))
+        }
+    }
+}
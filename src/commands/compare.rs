@@ -0,0 +1,75 @@
+use crate::helpers::response_builder::ResponseBuilder;
+use crate::helpers::statics::{
+    lookup_text_style, split_translations, BIBLES, COMPARE_TRANSLATION_LIMIT,
+    DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT,
+};
+use crate::helpers::Config;
+use bible::scripture::bible::Verse;
+use bible::scripture::compare::compare as compare_translations;
+use bible::scripture::reference::Reference;
+
+pub async fn compare(channel: &str, display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Compare Help: Shows a verse in several translations side by side, your preferred translation and a few others unless you name them. Usage: !compare john 1:1 | !compare john 1:1 kjv web";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let (reference_words, mut translations) = split_translations(&params);
+
+    if translations.is_empty() {
+        let preferred = Config::load(display_name)
+            .get_translation()
+            .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string());
+        let mut others: Vec<&String> = BIBLES.keys().filter(|name| **name != preferred).collect();
+        others.sort();
+        translations = std::iter::once(preferred)
+            .chain(others.into_iter().cloned())
+            .take(*COMPARE_TRANSLATION_LIMIT)
+            .collect();
+    }
+
+    let reference: Reference = match reference_words.join(" ").parse() {
+        Ok(reference) => reference,
+        Err(e) => return Some(e.to_string()),
+    };
+    let bibles = translations.iter().filter_map(|name| {
+        BIBLES
+            .get(name)
+            .map(|bible| (name.as_str(), bible.as_ref()))
+    });
+    let comparisons = compare_translations(&reference, bibles);
+
+    // Translations without the passage are left out, unless none have it.
+    let style = lookup_text_style(channel);
+    let styled: Vec<(&str, Vec<Verse>)> = comparisons
+        .iter()
+        .filter_map(|comparison| {
+            comparison.verses.as_ref().ok().map(|verses| {
+                (
+                    comparison.translation,
+                    ResponseBuilder::styled(verses, style),
+                )
+            })
+        })
+        .collect();
+    let passages: Vec<(&str, &[Verse])> = styled
+        .iter()
+        .map(|(translation, verses)| (*translation, verses.as_slice()))
+        .collect();
+    if passages.is_empty() {
+        return comparisons
+            .into_iter()
+            .find_map(|comparison| comparison.verses.err())
+            .map(|e| e.to_string());
+    }
+
+    let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + 1);
+    Some(ResponseBuilder::build_comparison(
+        &reference.to_string(),
+        &passages,
+        character_limit,
+    ))
+}
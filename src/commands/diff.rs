@@ -0,0 +1,81 @@
+use crate::helpers::statics::{
+    split_translations, BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT,
+};
+use crate::helpers::Config;
+use bible::scripture::diff::diff as diff_translations;
+use bible::scripture::reference::Reference;
+
+pub async fn diff(display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Diff Help: Lists the words that differ between two translations of a passage, compared with your preferred translation when you only name one. Usage: !diff john 3:16 kjv web | !diff psalm 23 web";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let (reference_words, mut translations) = split_translations(&params);
+    if translations.len() < 2 {
+        let preferred = Config::load(display_name)
+            .get_translation()
+            .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string());
+        let mut others: Vec<&String> = BIBLES
+            .keys()
+            .filter(|name| **name != preferred && !translations.contains(name))
+            .collect();
+        others.sort();
+        if !translations.contains(&preferred) {
+            translations.insert(0, preferred);
+        }
+        translations.extend(others.into_iter().cloned());
+    }
+    let (Some(left_name), Some(right_name)) = (translations.first(), translations.get(1)) else {
+        return Some("Diff needs two translations to compare.".to_string());
+    };
+
+    let reference: Reference = match reference_words.join(" ").parse() {
+        Ok(reference) => reference,
+        Err(e) => return Some(e.to_string()),
+    };
+    let (Some(left), Some(right)) = (BIBLES.get(left_name), BIBLES.get(right_name)) else {
+        eprintln!("No Bible version found for translation");
+        return None;
+    };
+    let passage = match diff_translations(&reference, left, right) {
+        Ok(passage) => passage,
+        Err(e) => return Some(e.to_string()),
+    };
+
+    let changed_words = passage.changed_words();
+    if changed_words == 0 {
+        return Some(format!(
+            "{} reads the same in the {} and {}.",
+            reference, left_name, right_name
+        ));
+    }
+
+    let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + 1);
+    let mut response = format!(
+        "{} {} vs {}, {} words differ.",
+        reference, left_name, right_name, changed_words
+    );
+    for (name, runs) in [
+        (left_name, passage.left_only()),
+        (right_name, passage.right_only()),
+    ] {
+        let label = format!(" Only {}:", name);
+        if runs.is_empty() || response.len() + label.len() + 2 > character_limit {
+            continue;
+        }
+        response.push_str(&label);
+        for (i, run) in runs.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            if response.len() + separator.len() + run.len() > character_limit {
+                break;
+            }
+            response.push_str(separator);
+            response.push_str(run);
+        }
+    }
+    Some(response)
+}
@@ -1,25 +1,47 @@
+pub mod book;
 pub mod channelinfo;
 pub mod commandprefix;
+pub mod compare;
+pub mod diff;
 pub mod gospel;
 pub mod help;
 pub mod myinfo;
 pub mod next;
+pub mod outline;
+pub mod plan;
 pub mod previous;
 pub mod random;
+pub mod search;
+pub mod strongs;
+pub mod suggestions;
 pub mod support;
+pub mod textstyle;
 pub mod translation;
 pub mod votd;
+pub mod word;
+pub mod xref;
 
+pub use self::book::book;
 pub use self::channelinfo::channelinfo;
 pub use self::commandprefix::commandprefix;
+pub use self::compare::compare;
+pub use self::diff::diff;
 pub use self::gospel::evangelio;
 pub use self::gospel::evangelium;
 pub use self::gospel::gospel;
 pub use self::help::help;
 pub use self::myinfo::myinfo;
 pub use self::next::next;
+pub use self::outline::outline;
+pub use self::plan::plan;
 pub use self::previous::previous;
 pub use self::random::random;
+pub use self::search::search;
+pub use self::strongs::strongs;
+pub use self::suggestions::suggestions;
 pub use self::support::support;
+pub use self::textstyle::textstyle;
 pub use self::translation::translation;
 pub use self::votd::votd;
+pub use self::word::word;
+pub use self::xref::xref;
@@ -1,4 +1,4 @@
-use crate::helpers::statics::{BIBLES, REPLY_CHARACTER_LIMIT};
+use crate::helpers::statics::{lookup_text_style, BIBLES, REPLY_CHARACTER_LIMIT};
 use crate::helpers::{response_builder::ResponseBuilder, Config};
 use bible::scripture::bible::Bible;
 
@@ -25,6 +25,8 @@ pub async fn next(channel: &str, display_name: &str, params: Vec<String>) -> Opt
                 .get(&translation)
                 .and_then(|bible_arc| {
                     let bible: &Bible = &*bible_arc;
+                    // Last verses are saved in KJV numbering, which may differ from this translation.
+                    let last_verse = bible.from_kjv_reference(&last_verse);
                     let verses = bible.get_next_scripture(&last_verse, verses_count);
 
                     if verses.is_empty() {
@@ -32,9 +34,10 @@ pub async fn next(channel: &str, display_name: &str, params: Vec<String>) -> Opt
                     } else {
                         let adjusted_character_limit =
                             *REPLY_CHARACTER_LIMIT - (display_name.len() + 1);
+                        let verses = &ResponseBuilder::styled_with_heading(verses, lookup_text_style(channel));
                         let response_output =
-                            ResponseBuilder::build(&verses, adjusted_character_limit, &translation);
-                        config.set_last_verse(&verses.last().unwrap().reference);
+                            ResponseBuilder::build(verses, adjusted_character_limit, &translation);
+                        config.set_last_verse(&bible.to_kjv_reference(&verses.last().unwrap().reference));
                         config.add_account_metrics_scriptures();
 
                         if !channel.eq_ignore_ascii_case(display_name) {
@@ -0,0 +1,76 @@
+use crate::helpers::statics::{
+    split_translations, BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT,
+};
+use crate::helpers::Config;
+use bible::scripture::bible::Bible;
+use bible::scripture::outline::outline as outline_sections;
+use bible::scripture::reference::Reference;
+
+pub async fn outline(display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Outline Help: Lists the section headings of a passage with their verses, from a translation that has headings. Usage: !outline romans 8 | !outline john 3-4 | !outline psalm 119 web";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let (params, translations) = split_translations(&params);
+    let reference: Reference = match params.join(" ").parse() {
+        Ok(reference) => reference,
+        Err(e) => return Some(e.to_string()),
+    };
+    let Some((translation, bible)) = headed_bible(display_name, translations) else {
+        return Some("No translation with section headings is loaded.".to_string());
+    };
+    let verses = match bible.get_verses(&reference) {
+        Ok(verses) => verses,
+        Err(e) => return Some(e.to_string()),
+    };
+
+    let sections = outline_sections(verses);
+    if sections.is_empty() {
+        return Some(format!(
+            "{} has no section headings in {}.",
+            reference, translation
+        ));
+    }
+
+    // Chapters are only shown when the passage has more than one.
+    let chapters =
+        verses.first().map(|verse| verse.chapter) != verses.last().map(|verse| verse.chapter);
+    let header = format!("{} {} - ", reference, translation);
+    let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + 1 + header.len());
+    let mut response = String::new();
+    for section in sections {
+        let part = format!(
+            "{} {}",
+            section.verse_range(chapters),
+            section.headings.join(" / ")
+        );
+        if !response.is_empty() && response.len() + 3 + part.len() > character_limit {
+            break;
+        }
+        if !response.is_empty() {
+            response.push_str(" | ");
+        }
+        response.push_str(&part);
+    }
+    Some(format!("{}{}", header, response))
+}
+
+// The named or preferred translation when it has section headings, otherwise the first loaded
+// translation that does.
+fn headed_bible(display_name: &str, translations: Vec<String>) -> Option<(String, &'static Bible)> {
+    let preferred = translations.into_iter().next().unwrap_or_else(|| {
+        Config::load(display_name)
+            .get_translation()
+            .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string())
+    });
+    let mut names: Vec<&String> = BIBLES.keys().collect();
+    names.sort_by_key(|name| (**name != preferred, *name));
+    names.into_iter().find_map(|name| {
+        let bible = BIBLES.get(name)?;
+        bible.has_headings().then(|| (name.clone(), bible.as_ref()))
+    })
+}
@@ -0,0 +1,166 @@
+use crate::helpers::config::ReadingProgress;
+use crate::helpers::response_builder::ResponseBuilder;
+use crate::helpers::statics::{
+    find_reading_plan, lookup_text_style, BIBLES, DEFAULT_TRANSLATION, READING_PLANS,
+    REPLY_CHARACTER_LIMIT,
+};
+use crate::helpers::Config;
+use bible::scripture::plan::ReadingPlan;
+use bible::scripture::versification::Versification;
+use chrono::Local;
+
+pub async fn plan(channel: &str, display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Plan Help: Follow a Bible reading plan day by day. !plan today shows the day's passages in your preferred translation, use !next to keep reading, and !plan done marks the day read. Usage: !plan start nt90 | !plan today | !plan done | !plan progress | !plan stop | !plan list";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let mut config = Config::load(display_name);
+    let name = params[1..].join(" ");
+    let progress = config.get_reading_plan();
+    // The plan being followed, which may have gone if a plan file was removed.
+    let current = progress.as_ref().and_then(|progress| {
+        let plan = find_reading_plan(progress.name.as_deref()?)?;
+        Some((progress.clone(), plan))
+    });
+    let not_started =
+        "You are not following a reading plan, start one with !plan start and one of: ";
+
+    match params[0].to_lowercase().as_str() {
+        "list" => Some(format!("Reading plans: {}", plan_list())),
+        "start" => {
+            let Some(plan) = find_reading_plan(&name) else {
+                return Some(format!("Start a reading plan with one of: {}", plan_list()));
+            };
+            config.set_reading_plan(Some(ReadingProgress::new(&plan.name)));
+            config.add_note(format!("Started the {} reading plan", plan.name));
+            Some(format!(
+                "Started {}, {} days. Use !plan today for day 1.",
+                plan.title,
+                plan.len()
+            ))
+        }
+        "today" => {
+            let Some((progress, plan)) = current else {
+                return Some(format!("{}{}", not_started, plan_list()));
+            };
+            today(channel, display_name, &mut config, &progress, plan)
+        }
+        "done" => {
+            let Some((mut progress, plan)) = current else {
+                return Some(format!("{}{}", not_started, plan_list()));
+            };
+            progress.mark_read(Local::now().date_naive());
+            let day = progress.day.unwrap_or(1);
+            let streak = progress.streak.unwrap_or(0);
+            if day > plan.len() {
+                config.set_reading_plan(None);
+                config.add_note(format!("Finished the {} reading plan", plan.name));
+                return Some(format!("You finished {}, well done!", plan.title));
+            }
+            config.set_reading_plan(Some(progress));
+            Some(format!(
+                "Day {} of {} done, {} day streak. Next: {}",
+                day - 1,
+                plan.len(),
+                streak,
+                readings(plan, day)
+            ))
+        }
+        "progress" => {
+            let Some((progress, plan)) = current else {
+                return Some(format!("{}{}", not_started, plan_list()));
+            };
+            let day = progress.day.unwrap_or(1);
+            Some(format!(
+                "{}: day {} of {}, {}% read, {} day streak (best {}), started {}.",
+                plan.title,
+                day,
+                plan.len(),
+                (day - 1) * 100 / plan.len().max(1),
+                progress.current_streak(Local::now().date_naive()),
+                progress.best_streak.unwrap_or(0),
+                progress
+                    .start_date
+                    .map_or("-".to_string(), |date| date.format("%Y/%m/%d").to_string())
+            ))
+        }
+        "stop" => {
+            let Some((_, plan)) = current else {
+                return Some("You are not following a reading plan.".to_string());
+            };
+            config.set_reading_plan(None);
+            config.add_note(format!("Stopped the {} reading plan", plan.name));
+            Some(format!("Stopped {}.", plan.title))
+        }
+        _ => Some(help_message.to_string()),
+    }
+}
+
+// Lists the day's passages and starts the first one in the preferred translation, saving where
+// it stopped so !next carries on reading.
+fn today(
+    channel: &str,
+    display_name: &str,
+    config: &mut Config,
+    progress: &ReadingProgress,
+    plan: &ReadingPlan,
+) -> Option<String> {
+    let day = progress.day.unwrap_or(1);
+    let header = format!(
+        "Day {} of {}, {}: {}",
+        day,
+        plan.len(),
+        plan.title,
+        readings(plan, day)
+    );
+    let translation = config
+        .get_translation()
+        .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string());
+    let Some(bible) = BIBLES.get(&translation) else {
+        return Some(header);
+    };
+    let Some(reference) = plan.day(day).and_then(|references| references.first()) else {
+        return Some(header);
+    };
+
+    // Plans are written in KJV numbering, which may differ from this translation.
+    let reference = Versification::Kjv.convert_reference(reference, bible.versification());
+    let verses = match bible.get_verses(&reference) {
+        Ok(verses) if !verses.is_empty() => verses,
+        _ => return Some(header),
+    };
+    let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + header.len() + 4);
+    let verses = &ResponseBuilder::styled_with_heading(verses, lookup_text_style(channel));
+    let response_output = ResponseBuilder::build(verses, character_limit, &translation);
+    config.set_last_verse(&bible.to_kjv_reference(&response_output.last_verse));
+    config.add_account_metrics_scriptures();
+
+    if !channel.eq_ignore_ascii_case(display_name) {
+        Config::load(channel).add_channel_metrics_scriptures();
+    } else {
+        config.add_channel_metrics_scriptures();
+    }
+
+    Some(format!("{} - {}", header, response_output.truncated))
+}
+
+fn readings(plan: &ReadingPlan, day: usize) -> String {
+    plan.day(day)
+        .unwrap_or_default()
+        .iter()
+        .map(|reference| reference.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn plan_list() -> String {
+    READING_PLANS
+        .iter()
+        .map(|plan| format!("{} ({})", plan.name, plan.title))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,4 +1,4 @@
-use crate::helpers::statics::{BIBLES, REPLY_CHARACTER_LIMIT};
+use crate::helpers::statics::{lookup_text_style, BIBLES, REPLY_CHARACTER_LIMIT};
 use crate::helpers::{response_builder::ResponseBuilder, Config};
 use bible::scripture::bible::Bible;
 
@@ -25,6 +25,8 @@ pub async fn previous(channel: &str, display_name: &str, params: Vec<String>) ->
                 .get(&translation)
                 .and_then(|bible_arc| {
                     let bible: &Bible = &*bible_arc;
+                    // Last verses are saved in KJV numbering, which may differ from this translation.
+                    let last_verse = bible.from_kjv_reference(&last_verse);
                     let verses = bible.get_previous_scripture(&last_verse, verses_count);
 
                     match verses.is_empty() {
@@ -32,12 +34,13 @@ pub async fn previous(channel: &str, display_name: &str, params: Vec<String>) ->
                         false => {
                             let adjusted_character_limit =
                                 *REPLY_CHARACTER_LIMIT - (display_name.len() + 1);
+                            let verses = &ResponseBuilder::styled_with_heading(verses, lookup_text_style(channel));
                             let response_output = ResponseBuilder::build(
-                                &verses,
+                                verses,
                                 adjusted_character_limit,
                                 &translation,
                             );
-                            config.set_last_verse(&verses.first().unwrap().reference);
+                            config.set_last_verse(&bible.to_kjv_reference(&verses.first().unwrap().reference));
                             config.add_account_metrics_scriptures();
 
                             if !channel.eq_ignore_ascii_case(display_name) {
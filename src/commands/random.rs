@@ -1,10 +1,13 @@
-use crate::helpers::statics::{BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT};
+use crate::helpers::statics::{
+    lookup_text_style, BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT,
+};
 use crate::helpers::{response_builder::ResponseBuilder, Config};
 use bible::scripture::bible::Bible;
+use bible::scripture::scope::Scope;
 
 pub async fn random(channel: &str, display_name: &str, params: Vec<String>) -> Option<String> {
     let help_message =
-        "Random Help: Retrieves a random verse from scripture using your preferred translation. Usage: !random";
+        "Random Help: Retrieves a random verse from scripture, or from a book, chapter, testament or the gospels, using your preferred translation. Add passage for 2-3 verses. Usage: !random | !random psalms | !random nt | !random proverbs 3 | !random gospels passage";
     if params
         .get(0)
         .map_or(false, |p| p == "?" || p.to_lowercase() == "help")
@@ -12,6 +15,15 @@ pub async fn random(channel: &str, display_name: &str, params: Vec<String>) -> O
         return Some(help_message.to_string());
     }
 
+    // "passage" anywhere asks for a few verses rather than one, e.g. "!random psalms passage".
+    let (passage, scope_words): (Vec<String>, Vec<String>) =
+        params.into_iter().partition(|p| p.eq_ignore_ascii_case("passage"));
+    let verses = if passage.is_empty() { 1..=1 } else { 2..=3 };
+    let scope: Scope = match scope_words.join(" ").parse() {
+        Ok(scope) => scope,
+        Err(e) => return Some(e.to_string()),
+    };
+
     let mut config = Config::load(&display_name);
     let translation = config
         .get_translation()
@@ -19,15 +31,19 @@ pub async fn random(channel: &str, display_name: &str, params: Vec<String>) -> O
 
     if let Some(bible_arc) = BIBLES.get(&translation) {
         let bible: &Bible = &*bible_arc;
-        let random_scripture = bible.random_scripture();
+        let random_scripture = match bible.random_passage(&scope, verses) {
+            Ok(verses) => verses,
+            Err(e) => return Some(e.to_string()),
+        };
 
         if random_scripture.is_empty() {
             None
         } else {
             let adjusted_character_limit = *REPLY_CHARACTER_LIMIT - (&display_name.len() + 1);
+            let random_scripture = &ResponseBuilder::styled_with_heading(random_scripture, lookup_text_style(channel));
             let response_output =
-                ResponseBuilder::build(&random_scripture, adjusted_character_limit, &translation);
-            config.set_last_verse(&random_scripture.last().unwrap().reference);
+                ResponseBuilder::build(random_scripture, adjusted_character_limit, &translation);
+            config.set_last_verse(&bible.to_kjv_reference(&random_scripture.last().unwrap().reference));
             config.add_account_metrics_scriptures();
 
             if !channel.eq_ignore_ascii_case(display_name) {
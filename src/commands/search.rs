@@ -0,0 +1,47 @@
+use crate::helpers::statics::{BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT};
+use crate::helpers::Config;
+use bible::scripture::bible::Bible;
+
+pub async fn search(display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Search Help: Finds the verses that best match your words using your preferred translation. Put a phrase in quotes and use OR between alternatives. Usage: !search be still and know | !search \"living water\" | !search charity OR love";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let config = Config::load(display_name);
+    let translation = config
+        .get_translation()
+        .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string());
+    let query = params.join(" ");
+
+    if let Some(bible_arc) = BIBLES.get(&translation) {
+        let bible: &Bible = bible_arc;
+        let verses = bible.search(&query, 25);
+        if verses.is_empty() {
+            return Some(format!("No verses found for {} in the {}.", query, translation));
+        }
+
+        let mut response = format!(
+            "{} verses found for {} ({}):",
+            bible.search_count(&query),
+            query,
+            translation
+        );
+        let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + 1);
+        for (i, verse) in verses.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            if response.len() + separator.len() + verse.reference.len() > character_limit {
+                break;
+            }
+            response.push_str(separator);
+            response.push_str(&verse.reference);
+        }
+        Some(response)
+    } else {
+        eprintln!("No Bible version found for translation");
+        None
+    }
+}
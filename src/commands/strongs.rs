@@ -0,0 +1,21 @@
+use crate::helpers::statics::LEXICON;
+use bible::scripture::strongs::StrongsNumber;
+
+pub async fn strongs(params: Vec<String>) -> Option<String> {
+    let help_message = "Strongs Help: Shows the Hebrew or Greek word for a Strong's number with its transliteration and meaning. Use !word to find the numbers in a verse. Usage: !strongs G26 | !strongs H430";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let number: StrongsNumber = match params.join("").parse() {
+        Ok(number) => number,
+        Err(e) => return Some(e.to_string()),
+    };
+    Some(match LEXICON.get(number) {
+        Some(entry) => entry.to_string(),
+        None => format!("{} is not in the lexicon.", number),
+    })
+}
@@ -0,0 +1,27 @@
+use crate::helpers::Config;
+
+pub async fn suggestions(display_name: &str, params: Vec<String>) -> (Option<String>, bool) {
+    let help_message = "Suggestions Help: Displays or sets whether your channel answers a misspelt book with a suggestion, e.g. \"Mathews is not a book of the Bible, did you mean Matthew 5:9?\", default: on. Usage: !suggestions | !suggestions off";
+
+    let mut config = Config::load(display_name);
+    let state = |on: bool| if on { "on" } else { "off" };
+
+    let message = match params.first().map(|p| p.to_lowercase()) {
+        None => format!(
+            "Suggestions for the {} channel are: {}",
+            display_name,
+            state(config.get_suggestions())
+        ),
+        Some(param) if param == "?" || param == "help" => help_message.to_string(),
+        Some(param) if param == "on" || param == "off" => {
+            config.set_suggestions(param == "on");
+            format!(
+                "Suggestions for the {} channel are now: {}",
+                display_name, param
+            )
+        }
+        Some(param) => format!("Error: {} is not on or off. {}", param, help_message),
+    };
+
+    (Some(message), Config::load(display_name).get_suggestions())
+}
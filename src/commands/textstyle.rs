@@ -0,0 +1,32 @@
+use crate::helpers::Config;
+use bible::scripture::markup::TextStyle;
+
+pub async fn textstyle(display_name: &str, params: Vec<String>) -> (Option<String>, TextStyle) {
+    let help_message = "TextStyle Help: Displays or sets how scripture in your channel shows the words of Jesus, the words translators supplied and footnotes, for translations that mark them, default: plain. plain leaves them out, brackets shows ‹words of Jesus› and [supplied words], unicode shows them in bold and italic letters and markup also adds {footnotes}. Usage: !textstyle | !textstyle brackets";
+
+    let mut config = Config::load(display_name);
+    let style = config.get_text_style();
+    let styles: Vec<String> = TextStyle::ALL.iter().map(|s| s.to_string()).collect();
+
+    let message = match params.first().map(|p| p.to_lowercase()) {
+        None => format!(
+            "The text style for the {} channel is: {}. Available styles: {}",
+            display_name,
+            style,
+            styles.join(", ")
+        ),
+        Some(param) if param == "?" || param == "help" => help_message.to_string(),
+        Some(param) => match param.parse::<TextStyle>() {
+            Ok(new_style) => {
+                config.set_text_style(new_style);
+                format!(
+                    "The new text style for the {} channel is: {}",
+                    display_name, new_style
+                )
+            }
+            Err(e) => format!("Error: {}. Available styles: {}", e, styles.join(", ")),
+        },
+    };
+
+    (Some(message), Config::load(display_name).get_text_style())
+}
@@ -1,12 +1,14 @@
-use crate::helpers::statics::{BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT};
+use crate::helpers::statics::{
+    lookup_text_style, BIBLES, DEFAULT_TRANSLATION, REPLY_CHARACTER_LIMIT,
+};
 use crate::helpers::{
     response_builder::ResponseBuilder, webscraper::fetch_verse_of_the_day, Config,
 };
 use bible::scripture::bible::Bible;
 
 pub async fn votd(channel: &str, display_name: &str, params: Vec<String>) -> Option<String> {
-    let help_message = "Verse of the Day (VOTD) Help: Retrieves the daily verse from an external source or allows you to set it manually. Use 'auto' to reset it to use the external source. Usage: !votd | !votd gen 1:1 | !votd auto";
-    let auto_message = "Verse of the Day reset it to use the external source.";
+    let help_message = "Verse of the Day (VOTD) Help: Retrieves the daily verse from the built-in list or an external source or allows you to set it manually. Use 'auto' to reset it to the daily verse. Usage: !votd | !votd gen 1:1 | !votd auto";
+    let auto_message = "Verse of the Day reset it to use the daily verse.";
     match params.get(0) {
         Some(p) if p == "?" || p.to_lowercase() == "help" => return Some(help_message.to_string()),
         Some(a) if a.to_lowercase() == "auto" => {
@@ -23,21 +25,21 @@ pub async fn votd(channel: &str, display_name: &str, params: Vec<String>) -> Opt
 
             if let Some(bible_arc) = BIBLES.get(&translation) {
                 let bible: &Bible = &*bible_arc;
-                let verses = bible.get_scripture(&reference);
-
-                if verses.is_empty() {
-                    Some(format!("Invalid verse reference."))
-                } else {
-                    let adjusted_character_limit =
-                        *REPLY_CHARACTER_LIMIT - (&display_name.len() + 1);
-                    let response_output =
-                        ResponseBuilder::build(&verses, adjusted_character_limit, &translation);
+                match bible.lookup(&reference) {
+                    Err(e) => Some(format!("Invalid verse reference, {}.", e)),
+                    Ok(verses) => {
+                        let adjusted_character_limit =
+                            *REPLY_CHARACTER_LIMIT - (&display_name.len() + 1);
+                        let verses = &ResponseBuilder::styled_with_heading(verses, lookup_text_style(channel));
+                        let response_output =
+                            ResponseBuilder::build(verses, adjusted_character_limit, &translation);
 
-                    config.set_votd(Some(reference));
-                    Some(format!(
-                        "Verse of the day for {} channel, manually set to: {}",
-                        &display_name, response_output.truncated
-                    ))
+                        config.set_votd(Some(reference));
+                        Some(format!(
+                            "Verse of the day for {} channel, manually set to: {}",
+                            &display_name, response_output.truncated
+                        ))
+                    }
                 }
             } else {
                 Some("No Bible version found for translation.".to_string())
@@ -69,12 +71,13 @@ pub async fn votd(channel: &str, display_name: &str, params: Vec<String>) -> Opt
                         } else {
                             let adjusted_character_limit =
                                 *REPLY_CHARACTER_LIMIT - (&display_name.len() + 1);
+                            let verses = &ResponseBuilder::styled_with_heading(verses, lookup_text_style(channel));
                             let response_output = ResponseBuilder::build(
-                                &verses,
+                                verses,
                                 adjusted_character_limit,
                                 &translation,
                             );
-                            config.set_last_verse(&verses.last().unwrap().reference);
+                            config.set_last_verse(&bible.to_kjv_reference(&verses.last().unwrap().reference));
                             config.add_account_metrics_scriptures();
 
                             if !channel.eq_ignore_ascii_case(display_name) {
@@ -0,0 +1,95 @@
+use crate::helpers::statics::{
+    split_translations, BIBLES, DEFAULT_TRANSLATION, LEXICON, REPLY_CHARACTER_LIMIT,
+};
+use crate::helpers::Config;
+use bible::scripture::bible::Bible;
+use bible::scripture::reference::Reference;
+
+pub async fn word(display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Word Help: Shows the Hebrew or Greek behind a word of a verse, with its Strong's number, transliteration and meaning, from a translation tagged with Strong's numbers. Leave out the word to list the verse's numbers. Usage: !word john 3:16 love | !word gen 1:1 beginning | !word psalm 23:1";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    // The longest run of words from the start that is a reference, with the rest the word asked
+    // about, e.g. "1 john 4:8 love" is 1 John 4:8 and "love".
+    let (params, translations) = split_translations(&params);
+    let Some((reference, word)) = (1..=params.len()).rev().find_map(|split| {
+        let reference: Reference = params[..split].join(" ").parse().ok()?;
+        Some((reference, params[split..].join(" ")))
+    }) else {
+        return params
+            .join(" ")
+            .parse::<Reference>()
+            .err()
+            .map(|e| e.to_string());
+    };
+
+    let Some((translation, bible)) = tagged_bible(display_name, translations) else {
+        return Some("No translation with Strong's numbers is loaded.".to_string());
+    };
+    let verses = match bible.get_verses(&reference) {
+        Ok(verses) => verses,
+        Err(e) => return Some(e.to_string()),
+    };
+
+    let header = format!("{} {} - ", reference, translation);
+    let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + 1 + header.len());
+    let mut parts = Vec::new();
+    if word.is_empty() {
+        // Without a word, each tagged word of the passage with its numbers.
+        for tagged in verses.iter().flat_map(|verse| &verse.words) {
+            let numbers: Vec<String> = tagged.strongs.iter().map(|n| n.to_string()).collect();
+            parts.push(format!("{} {}", tagged.text, numbers.join(" ")));
+        }
+    } else {
+        for tagged in verses.iter().flat_map(|verse| verse.find_words(&word)) {
+            for number in &tagged.strongs {
+                parts.push(match LEXICON.get(*number) {
+                    Some(entry) => format!("{}: {}", tagged.text, entry),
+                    None => format!("{}: {}", tagged.text, number),
+                });
+            }
+        }
+    }
+    if parts.is_empty() {
+        return Some(if word.is_empty() {
+            format!("{} has no Strong's numbers in {}.", reference, translation)
+        } else {
+            format!("\"{}\" is not a tagged word of {}.", word, reference)
+        });
+    }
+
+    let separator = if word.is_empty() { ", " } else { " | " };
+    let mut response = String::new();
+    for part in parts {
+        let length = response.len() + separator.len() + part.len();
+        if !response.is_empty() && length > character_limit {
+            break;
+        }
+        if !response.is_empty() {
+            response.push_str(separator);
+        }
+        response.push_str(&part);
+    }
+    Some(format!("{}{}", header, response))
+}
+
+// The named or preferred translation when it is tagged with Strong's numbers, otherwise the first
+// loaded translation that is.
+fn tagged_bible(display_name: &str, translations: Vec<String>) -> Option<(String, &'static Bible)> {
+    let preferred = translations.into_iter().next().unwrap_or_else(|| {
+        Config::load(display_name)
+            .get_translation()
+            .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string())
+    });
+    let mut names: Vec<&String> = BIBLES.keys().collect();
+    names.sort_by_key(|name| (**name != preferred, *name));
+    names.into_iter().find_map(|name| {
+        let bible = BIBLES.get(name)?;
+        bible.has_strongs().then(|| (name.clone(), bible.as_ref()))
+    })
+}
@@ -0,0 +1,63 @@
+use crate::helpers::statics::{
+    split_translations, BIBLES, CROSS_REFERENCES, CROSS_REFERENCE_LIMIT, DEFAULT_TRANSLATION,
+    REPLY_CHARACTER_LIMIT,
+};
+use crate::helpers::Config;
+use bible::scripture::reference::Reference;
+use bible::scripture::versification::Versification;
+
+pub async fn xref(display_name: &str, params: Vec<String>) -> Option<String> {
+    let help_message = "Xref Help: Lists the passages most related to a verse or passage, numbered as in your preferred translation or the one named. Usage: !xref john 3:16 | !xref romans 8:28-30 | !xref psalm 23 nabre";
+    if params
+        .first()
+        .map_or(true, |p| p == "?" || p.to_lowercase() == "help")
+    {
+        return Some(help_message.to_string());
+    }
+
+    let (reference_words, translations) = split_translations(&params);
+    let translation = translations.into_iter().next().unwrap_or_else(|| {
+        Config::load(display_name)
+            .get_translation()
+            .unwrap_or_else(|| DEFAULT_TRANSLATION.to_string())
+    });
+    let reference: Reference = match reference_words.join(" ").parse() {
+        Ok(reference) => reference,
+        Err(e) => return Some(e.to_string()),
+    };
+    // Cross references are in KJV numbering, which may differ from this translation.
+    let versification = match BIBLES.get(&translation) {
+        Some(bible) => {
+            if let Err(e) = bible.get_verses(&reference) {
+                return Some(e.to_string());
+            }
+            bible.versification()
+        }
+        None => Versification::Kjv,
+    };
+
+    if CROSS_REFERENCES.is_empty() {
+        return Some("Cross references are not available.".to_string());
+    }
+    let kjv_reference = versification.convert_reference(&reference, Versification::Kjv);
+    let related = CROSS_REFERENCES.related(&kjv_reference, *CROSS_REFERENCE_LIMIT);
+    if related.is_empty() {
+        return Some(format!("No cross references found for {}.", reference));
+    }
+
+    // As many of the most related passages as fit in one reply.
+    let character_limit = *REPLY_CHARACTER_LIMIT - (display_name.len() + 1);
+    let mut response = format!("{} is related to", reference);
+    for (index, cross_reference) in related.iter().enumerate() {
+        let passage = Versification::Kjv
+            .convert_reference(&cross_reference.reference, versification)
+            .to_string();
+        let separator = if index == 0 { " " } else { ", " };
+        if response.len() + separator.len() + passage.len() > character_limit {
+            break;
+        }
+        response.push_str(separator);
+        response.push_str(&passage);
+    }
+    Some(response)
+}
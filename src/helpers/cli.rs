@@ -0,0 +1,167 @@
+use crate::helpers::env_variables::get_env_variable;
+use crate::helpers::print_color::PrintCommand;
+use crate::helpers::statics::{split_translations, BIBLES};
+use bible::scripture::books::Canon;
+use bible::scripture::diff::diff as diff_translations;
+use bible::scripture::reference::Reference;
+use bible::{binary_format, import, validation};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+const USAGE: &str = "Usage: chapterverse convert [bible file or USFM directory]...  Converts to the precompiled .cvb format, every translation in the bibles directory when none are given.
+       chapterverse validate [bible file or USFM directory]... [--canon protestant|catholic]  Checks translations for missing, duplicate and misordered verses and suspicious text, every translation in the bibles directory when none are given.  The canon is detected from the books present unless given.
+       chapterverse diff <reference> <translation> <translation> [--markdown]  Prints the word differences between two translations of a passage, e.g. for show notes.";
+
+/// Runs a command line subcommand such as `chapterverse convert bibles/KJV.csv`, returning the
+/// exit code, or None to start the bot when there is no subcommand.
+pub fn run(args: &[String]) -> Option<i32> {
+    let (command, paths) = args.split_first()?;
+    match command.as_str() {
+        "convert" => Some(convert(paths)),
+        "validate" => Some(validate(paths)),
+        "diff" => Some(diff(paths)),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Some(0)
+        }
+        _ => {
+            PrintCommand::Error.print_message("Unknown command", command);
+            println!("{}", USAGE);
+            Some(2)
+        }
+    }
+}
+
+// The files named on the command line, or every translation in the bibles directory, leaving
+// out precompiled files unless `precompiled` is set.
+fn bible_paths(paths: &[String], precompiled: bool) -> Option<Vec<PathBuf>> {
+    if !paths.is_empty() {
+        return Some(paths.iter().map(PathBuf::from).collect());
+    }
+    let bibles_directory = get_env_variable("IMPORT_BIBLES_PATH", "bibles");
+    match fs::read_dir(env::current_dir().unwrap_or_default().join(bibles_directory)) {
+        Ok(files) => Some(
+            files
+                .filter_map(|file| file.ok().map(|entry| entry.path()))
+                .filter(|path| import::preference(path).is_some_and(|preference| precompiled || preference > 0))
+                .collect(),
+        ),
+        Err(e) => {
+            PrintCommand::Error.print_message("Error reading bibles directory", &e.to_string());
+            None
+        }
+    }
+}
+
+fn convert(paths: &[String]) -> i32 {
+    let Some(paths) = bible_paths(paths, false) else {
+        return 1;
+    };
+
+    let mut failures = 0;
+    for path in paths {
+        let output = converted_path(&path);
+        let result = import::bible_import(&path.to_string_lossy()).and_then(|bible| {
+            binary_format::bible_export(&bible, &output.to_string_lossy())?;
+            Ok(bible.len())
+        });
+        match result {
+            Ok(verses) => PrintCommand::Info.print_message(
+                &format!("Converted {}", path.display()),
+                &format!("{} verses written to {}", verses, output.display()),
+            ),
+            Err(e) => {
+                failures += 1;
+                PrintCommand::Error.print_message(&format!("Error converting {}", path.display()), &e.to_string());
+            }
+        }
+    }
+    if failures == 0 { 0 } else { 1 }
+}
+
+fn validate(params: &[String]) -> i32 {
+    let (paths, canon) = match params.iter().position(|param| param == "--canon") {
+        Some(index) => match params.get(index + 1).map(|name| name.parse::<Canon>()) {
+            Some(Ok(canon)) => ([&params[..index], &params[index + 2..]].concat(), Some(canon)),
+            Some(Err(e)) => {
+                PrintCommand::Error.print_message("Error reading canon", &e);
+                return 2;
+            }
+            None => {
+                PrintCommand::Error.print_message("Missing canon", "--canon needs protestant or catholic");
+                return 2;
+            }
+        },
+        None => (params.to_vec(), None),
+    };
+    let Some(paths) = bible_paths(&paths, true) else {
+        return 1;
+    };
+
+    let mut failures = 0;
+    for path in paths {
+        match import::bible_import(&path.to_string_lossy()) {
+            Ok(bible) => {
+                let report = validation::validate_against(&bible, canon.unwrap_or_else(|| bible.canon()));
+                if !report.is_valid() {
+                    failures += 1;
+                }
+                println!("{}: {}", path.display(), report);
+            }
+            Err(e) => {
+                failures += 1;
+                PrintCommand::Error.print_message(&format!("Error importing {}", path.display()), &e.to_string());
+            }
+        }
+    }
+    if failures == 0 { 0 } else { 1 }
+}
+
+fn diff(params: &[String]) -> i32 {
+    let markdown = params.iter().any(|param| param == "--markdown");
+    let params: Vec<String> = params.iter().filter(|param| *param != "--markdown").cloned().collect();
+    let (reference_words, translations) = split_translations(&params);
+    let (Some(left_name), Some(right_name)) = (translations.first(), translations.get(1)) else {
+        PrintCommand::Error.print_message("Diff needs two loaded translations", &params.join(" "));
+        println!("{}", USAGE);
+        return 2;
+    };
+
+    let reference: Reference = match reference_words.join(" ").parse() {
+        Ok(reference) => reference,
+        Err(e) => {
+            PrintCommand::Error.print_message("Error reading reference", &e.to_string());
+            return 2;
+        }
+    };
+    match diff_translations(&reference, &BIBLES[left_name], &BIBLES[right_name]) {
+        Ok(passage) if markdown => {
+            println!("## {}: ~~{}~~ and **{}**\n\n{}", reference, left_name, right_name, passage.to_markdown());
+            0
+        }
+        Ok(passage) => {
+            println!("{} [-{}-] {{+{}+}}\n{}", reference, left_name, right_name, passage.to_text());
+            0
+        }
+        Err(e) => {
+            PrintCommand::Error.print_message("Error comparing translations", &e.to_string());
+            1
+        }
+    }
+}
+
+// KJV.csv and a USFM directory named WEB are written next to themselves as KJV.cvb and WEB.cvb.
+fn converted_path(path: &Path) -> PathBuf {
+    path.with_extension(binary_format::EXTENSION)
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn converted_path_replaces_extension() {
+        assert_eq!(converted_path(Path::new("bibles/KJV.csv")), PathBuf::from("bibles/KJV.cvb"));
+        assert_eq!(converted_path(Path::new("bibles/WEB")), PathBuf::from("bibles/WEB.cvb"));
+    }
+}
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Local, Utc};
+use bible::scripture::markup::TextStyle;
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fs;
 use std::path::Path;
@@ -57,6 +58,8 @@ pub struct Account {
     pub bible: Option<Bible>,
     #[serde(default)]
     pub metrics: Option<Metrics>,
+    #[serde(default)]
+    pub reading_plan: Option<ReadingProgress>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -77,6 +80,10 @@ pub struct Channel {
     pub metrics: Option<Metrics>,
     #[serde(default = "default_command_prefix")]
     pub command_prefix: Option<char>,
+    #[serde(default, serialize_with = "serialize_optional_string")]
+    pub text_style: Option<String>,
+    #[serde(default)]
+    pub suggestions: Option<bool>,
     #[serde(default)]
     pub modified_date: Option<DateTime<Utc>>,
 }
@@ -99,6 +106,58 @@ pub struct Bible {
     pub votd: Option<String>,
 }
 
+/// Where a user is in a reading plan.  `day` is the next day to read, counting from 1.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReadingProgress {
+    #[serde(default, serialize_with = "serialize_optional_string")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub day: Option<usize>,
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_read_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub streak: Option<u32>,
+    #[serde(default)]
+    pub best_streak: Option<u32>,
+}
+
+impl ReadingProgress {
+    pub fn new(name: &str) -> Self {
+        ReadingProgress {
+            name: Some(name.to_string()),
+            day: Some(1),
+            start_date: Some(Utc::now()),
+            last_read_date: None,
+            streak: Some(0),
+            best_streak: Some(0),
+        }
+    }
+
+    /// Moves on to the next day.  The streak counts the days in a row with a reading finished,
+    /// so reading two days of the plan on one day does not add to it.
+    pub fn mark_read(&mut self, today: NaiveDate) {
+        self.day = Some(self.day.unwrap_or(1) + 1);
+        let streak = match self.last_read_date {
+            Some(date) if date == today => self.streak.unwrap_or(1),
+            Some(date) if date.succ_opt() == Some(today) => self.streak.unwrap_or(0) + 1,
+            _ => 1,
+        };
+        self.streak = Some(streak);
+        self.best_streak = Some(self.best_streak.unwrap_or(0).max(streak));
+        self.last_read_date = Some(today);
+    }
+
+    /// The streak as of today, which is broken once a whole day passes without a reading.
+    pub fn current_streak(&self, today: NaiveDate) -> u32 {
+        match self.last_read_date {
+            Some(date) if date == today || date.succ_opt() == Some(today) => self.streak.unwrap_or(0),
+            _ => 0,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Metrics {
     #[serde(default)]
@@ -145,6 +204,7 @@ impl Config {
                     gospels_spanish: Some(0),
                     gospels_german: Some(0),
                 }),
+                reading_plan: None,
             }),
             channel: Some(Channel {
                 notes: None,
@@ -166,6 +226,8 @@ impl Config {
                     gospels_german: Some(0),
                 }),
                 command_prefix: Some('!'),
+                text_style: None,
+                suggestions: None,
                 modified_date: Some(now),
             }),
         }
@@ -410,6 +472,54 @@ impl Config {
         }
     }
 
+    /// How the channel shows words of Jesus, supplied words and footnotes, plain by default.
+    pub fn get_text_style(&self) -> TextStyle {
+        self.channel
+            .as_ref()
+            .and_then(|c| c.text_style.as_deref())
+            .and_then(|style| style.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_text_style(&mut self, style: TextStyle) {
+        if let Some(channel) = self.channel.as_mut() {
+            channel.text_style = Some(style.to_string());
+            channel.modified_date = Some(Utc::now());
+            self.save();
+        }
+    }
+
+    /// Whether the channel answers a misspelt book with "did you mean", on by default.
+    pub fn get_suggestions(&self) -> bool {
+        self.channel
+            .as_ref()
+            .and_then(|c| c.suggestions)
+            .unwrap_or(true)
+    }
+
+    pub fn set_suggestions(&mut self, suggestions: bool) {
+        if let Some(channel) = self.channel.as_mut() {
+            channel.suggestions = Some(suggestions);
+            channel.modified_date = Some(Utc::now());
+            self.save();
+        }
+    }
+
+    pub fn get_reading_plan(&self) -> Option<ReadingProgress> {
+        self.account
+            .as_ref()
+            .and_then(|acc| acc.reading_plan.clone())
+            .filter(|progress| progress.name.is_some())
+    }
+
+    pub fn set_reading_plan(&mut self, progress: Option<ReadingProgress>) {
+        if let Some(account) = self.account.as_mut() {
+            account.reading_plan = progress;
+            account.modified_date = Some(Utc::now());
+            self.save();
+        }
+    }
+
     pub fn get_votd(&self) -> Option<String> {
         self.channel
             .as_ref()
@@ -540,3 +650,24 @@ impl Config {
         }
     }
 }
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    #[test]
+    fn reading_streaks() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let mut progress = ReadingProgress::new("nt90");
+        progress.mark_read(day(1));
+        progress.mark_read(day(2));
+        progress.mark_read(day(2));
+        assert_eq!((progress.day, progress.streak), (Some(4), Some(2)));
+        assert_eq!(progress.current_streak(day(3)), 2);
+        assert_eq!(progress.current_streak(day(4)), 0);
+
+        progress.mark_read(day(5));
+        assert_eq!(progress.streak, Some(1));
+        assert_eq!(progress.best_streak, Some(2));
+    }
+}
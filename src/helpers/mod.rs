@@ -1,3 +1,4 @@
+pub mod cli;
 pub mod config;
 pub mod config_manager;
 pub mod env_variables;
@@ -5,6 +6,7 @@ pub mod metrics;
 pub mod print_color;
 pub mod response_builder;
 pub mod statics;
+pub mod votd_rotation;
 pub mod webscraper;
 
 pub use self::config::Config;
@@ -1,99 +1,429 @@
 use bible::scripture::bible::Verse;
+use bible::scripture::markup::TextStyle;
 
 pub struct ResponseOutput {
     pub truncated: String,
     pub remainder: String,
     pub last_verse: String,
+    pub pages: Vec<String>,
 }
 
 pub struct ResponseBuilder;
 
 impl ResponseBuilder {
     pub fn build(
-        verses: &Vec<Verse>,
+        verses: &[Verse],
         total_length: usize,
         bible_name_to_use: &str,
     ) -> ResponseOutput {
+        Self::build_pages(verses, total_length, bible_name_to_use, 1)
+    }
+
+    /// Splits the verses into up to `max_pages` chat sized replies, each ending with the
+    /// abbreviated reference it covers.  Verses too long for one reply are split between words.
+    pub fn build_pages(
+        verses: &[Verse],
+        total_length: usize,
+        bible_name_to_use: &str,
+        max_pages: usize,
+    ) -> ResponseOutput {
+        let first_verse = verses.first().unwrap();
         let last_verse = verses.last().unwrap();
-        let start_verse = verses.first().unwrap().verse;
-        let end_verse = last_verse.verse;
-        let reference = last_verse.reference.split(':').next().unwrap_or_default();
-        let abbreviation = last_verse
-            .abbreviation
-            .split(':')
-            .next()
-            .unwrap_or_default();
 
-        let scripture_reference = if start_verse == end_verse {
-            format!("{}:{} {}", reference, start_verse, bible_name_to_use)
-        } else {
-            format!(
-                "{}:{}-{} {}",
-                reference, start_verse, end_verse, bible_name_to_use
-            )
-        };
+        let scripture_reference = format!(
+            "{} {}",
+            Self::format_reference(first_verse, last_verse, false),
+            bible_name_to_use
+        );
+        let scriptures = Self::join_scriptures(verses);
+        let scripture_full = format!("{} - {}", scriptures, scripture_reference);
 
-        let scripture_reference_abbreviation = if start_verse == end_verse {
-            format!("{}:{} {}", abbreviation, start_verse, bible_name_to_use)
-        } else {
-            format!(
-                "{}:{}-{} {}",
-                abbreviation, start_verse, end_verse, bible_name_to_use
-            )
-        };
+        if scripture_full.len() <= total_length {
+            return ResponseOutput {
+                truncated: scripture_full.clone(),
+                remainder: String::new(),
+                last_verse: last_verse.reference.clone(),
+                pages: vec![scripture_full],
+            };
+        }
 
-        let scriptures = verses
-            .iter()
-            .map(|verse| verse.scripture.to_string())
-            .collect::<Vec<_>>()
-            .join(" ");
-        let scripture_full = format!("{} - {}", scriptures, scripture_reference);
+        let mut pages = Vec::new();
+        let mut index = 0;
+        // Byte offset into verses[index].scripture when a long verse was split over pages.
+        let mut offset = 0;
+        let mut last_shown = first_verse;
 
-        if scripture_full.len() > total_length {
-            let adjusted_length = total_length - scripture_reference_abbreviation.len();
+        while index < verses.len() && pages.len() < max_pages.max(1) {
+            let page_first = &verses[index];
             let mut scripture = String::new();
-            let mut last_verse = verses.last().unwrap();
-            for verse in verses {
-                if format!("{} {}", scripture, verse.scripture).len() < adjusted_length {
-                    scripture += " ";
-                    scripture += &verse.scripture;
-                    last_verse = verse;
+
+            while let Some(verse) = verses.get(index) {
+                let text = verse.scripture[offset..].trim_start();
+                let reference = format!(
+                    "{} {}",
+                    Self::format_reference(page_first, verse, true),
+                    bible_name_to_use
+                );
+                let available = total_length.saturating_sub(reference.len() + 3);
+                let candidate_length = match scripture.is_empty() {
+                    true => text.len(),
+                    false => scripture.len() + 1 + text.len(),
+                };
+
+                if candidate_length <= available {
+                    if !scripture.is_empty() {
+                        scripture.push(' ');
+                    }
+                    scripture.push_str(text);
+                    last_shown = verse;
+                    index += 1;
+                    offset = 0;
                 } else {
+                    if scripture.is_empty() {
+                        // A single verse longer than a reply, so carry the rest to the next page.
+                        let break_point = text
+                            .char_indices()
+                            .take_while(|&(idx, _)| idx <= available)
+                            .filter(|&(_, c)| c == ' ')
+                            .map(|(idx, _)| idx)
+                            .last()
+                            .unwrap_or_else(|| {
+                                (0..=available.min(text.len()))
+                                    .rev()
+                                    .find(|&idx| text.is_char_boundary(idx))
+                                    .unwrap_or(0)
+                            });
+                        scripture.push_str(&text[..break_point]);
+                        last_shown = verse;
+                        offset = verse.scripture.len() - text.len() + break_point;
+                    }
                     break;
                 }
             }
-            let break_point = scriptures
-                .char_indices()
-                .take_while(|&(idx, _)| idx <= adjusted_length)
-                .filter(|&(_, c)| c == ' ')
-                .map(|(idx, _)| idx)
-                .last()
-                .unwrap_or(adjusted_length);
-            let scripture_reference_abbreviation = if start_verse == end_verse {
-                format!("{}:{} {}", abbreviation, start_verse, bible_name_to_use)
+
+            pages.push(format!(
+                "{} - {} {}",
+                scripture,
+                Self::format_reference(page_first, last_shown, true),
+                bible_name_to_use
+            ));
+        }
+
+        let remainder = verses
+            .get(index)
+            .map(|verse| {
+                std::iter::once(verse.scripture[offset..].trim_start())
+                    .chain(verses[index + 1..].iter().map(|verse| verse.scripture.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+
+        ResponseOutput {
+            truncated: pages.first().cloned().unwrap_or_default(),
+            remainder,
+            last_verse: last_shown.reference.clone(),
+            pages,
+        }
+    }
+
+    /// Packs several passages into as few replies as possible, separated by " | ".  A passage
+    /// that does not fit in one reply is split over pages like `build_pages`.
+    pub fn build_passages(
+        passages: &[&[Verse]],
+        total_length: usize,
+        bible_name_to_use: &str,
+        max_pages: usize,
+    ) -> ResponseOutput {
+        let max_pages = max_pages.max(1);
+        let mut pages: Vec<String> = Vec::new();
+        let mut remainder = Vec::new();
+        let mut last_verse = String::new();
+
+        for passage in passages.iter().filter(|passage| !passage.is_empty()) {
+            // Once a passage has been cut short, everything after it is left for later.
+            if !remainder.is_empty() {
+                remainder.push(Self::join_scriptures(passage));
+                continue;
+            }
+
+            let output = Self::build_pages(passage, total_length, bible_name_to_use, max_pages);
+            let mut shown = 0;
+            for segment in &output.pages {
+                let page_count = pages.len();
+                match pages.last_mut() {
+                    Some(page) if page.len() + 3 + segment.len() <= total_length => {
+                        page.push_str(" | ");
+                        page.push_str(segment);
+                    }
+                    _ if page_count < max_pages => pages.push(segment.clone()),
+                    _ => break,
+                }
+                shown += 1;
+            }
+
+            if shown == 0 {
+                remainder.push(Self::join_scriptures(passage));
+                continue;
+            }
+            let output = if shown < output.pages.len() {
+                Self::build_pages(passage, total_length, bible_name_to_use, shown)
             } else {
-                format!(
-                    "{}:{}-{} {}",
-                    abbreviation, start_verse, last_verse.verse, bible_name_to_use
-                )
+                output
             };
-
-            ResponseOutput {
-                truncated: format!(
-                    "{} - {}",
-                    scripture,
-                    scripture_reference_abbreviation
-                ),
-                remainder: scriptures[break_point..].trim_start().to_string(),
-                last_verse: last_verse.reference.clone(),
+            last_verse = output.last_verse;
+            if !output.remainder.is_empty() {
+                remainder.push(output.remainder);
             }
+        }
+
+        ResponseOutput {
+            truncated: pages.first().cloned().unwrap_or_default(),
+            remainder: remainder.join(" "),
+            last_verse,
+            pages,
+        }
+    }
+
+    /// Copies of the verses with their text in a channel's text style, for building replies.
+    pub fn styled(verses: &[Verse], style: TextStyle) -> Vec<Verse> {
+        verses
+            .iter()
+            .map(|verse| Verse {
+                scripture: verse.render(style),
+                spans: Vec::new(),
+                ..verse.clone()
+            })
+            .collect()
+    }
+
+    /// Like `styled`, with the headings of the section the passage starts, if it starts one,
+    /// before its text, e.g. "A Psalm of David. — The LORD is my shepherd".
+    pub fn styled_with_heading(verses: &[Verse], style: TextStyle) -> Vec<Verse> {
+        let mut styled = Self::styled(verses, style);
+        if let Some(first) = styled.first_mut().filter(|first| !first.headings.is_empty()) {
+            first.scripture = format!("{} — {}", first.headings.join(" / "), first.scripture);
+        }
+        styled
+    }
+
+    /// Formats one reference in several translations as a single reply, e.g.
+    /// "John 1:1 - KJV: In the beginning... | WEB: In the beginning...".  Each translation gets an
+    /// even share of the space, anything a shorter text leaves goes to the longer ones, and text
+    /// that does not fit is cut between words.
+    pub fn build_comparison(
+        reference: &str,
+        passages: &[(&str, &[Verse])],
+        total_length: usize,
+    ) -> String {
+        let header = format!("{} - ", reference);
+        let texts: Vec<String> = passages
+            .iter()
+            .map(|(_, verses)| Self::join_scriptures(verses))
+            .collect();
+        // "KJV: " before each text and " | " between them.
+        let fixed_length = header.len()
+            + passages.iter().map(|(tag, _)| tag.len() + 2).sum::<usize>()
+            + 3 * passages.len().saturating_sub(1);
+        let mut available = total_length.saturating_sub(fixed_length);
+
+        let mut shortest_first: Vec<usize> = (0..texts.len()).collect();
+        shortest_first.sort_by_key(|&index| texts[index].len());
+        let mut shares = vec![0; texts.len()];
+        for (count, &index) in shortest_first.iter().enumerate() {
+            shares[index] = texts[index].len().min(available / (texts.len() - count));
+            available -= shares[index];
+        }
+
+        let parts: Vec<String> = passages
+            .iter()
+            .zip(&texts)
+            .zip(shares)
+            .map(|(((tag, _), text), share)| format!("{}: {}", tag, Self::shorten(text, share)))
+            .collect();
+        format!("{}{}", header, parts.join(" | "))
+    }
+
+    // Cuts text to at most `length` bytes at a space, ending with "..." when anything was cut.
+    fn shorten(text: &str, length: usize) -> String {
+        if text.len() <= length {
+            return text.to_string();
+        }
+        let limit = length.saturating_sub(3);
+        let break_point = text
+            .char_indices()
+            .take_while(|&(idx, _)| idx <= limit)
+            .filter(|&(_, c)| c == ' ')
+            .map(|(idx, _)| idx)
+            .last()
+            .unwrap_or(0);
+        format!("{}...", text[..break_point].trim_end())
+    }
+
+    fn join_scriptures(verses: &[Verse]) -> String {
+        verses
+            .iter()
+            .map(|verse| verse.scripture.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Formats the span from `first` to `last`, e.g. John 3:16, John 3:16-18, John 3:36-4:3
+    /// or Malachi 4:6-Matthew 1:1.  The abbreviated form drops the space: Jn3:36-4:3.
+    fn format_reference(first: &Verse, last: &Verse, abbreviated: bool) -> String {
+        let (start_book, end_book, separator) = if abbreviated {
+            (
+                Self::book_prefix(&first.abbreviation, first, ""),
+                Self::book_prefix(&last.abbreviation, last, ""),
+                "",
+            )
         } else {
-            ResponseOutput {
-                truncated: scripture_full,
-                remainder: String::new(),
-                last_verse: verses.last().unwrap().reference.clone(),
-            }
+            (
+                Self::book_prefix(&first.reference, first, " "),
+                Self::book_prefix(&last.reference, last, " "),
+                " ",
+            )
+        };
+
+        if first.book != last.book {
+            format!(
+                "{}{}{}:{}-{}{}{}:{}",
+                start_book,
+                separator,
+                first.chapter,
+                first.verse,
+                end_book,
+                separator,
+                last.chapter,
+                last.verse
+            )
+        } else if first.chapter != last.chapter {
+            format!(
+                "{}{}{}:{}-{}:{}",
+                start_book, separator, first.chapter, first.verse, last.chapter, last.verse
+            )
+        } else if first.verse != last.verse {
+            format!(
+                "{}{}{}:{}-{}",
+                start_book, separator, first.chapter, first.verse, last.verse
+            )
+        } else {
+            format!("{}{}{}:{}", start_book, separator, first.chapter, first.verse)
         }
     }
+
+    fn book_prefix<'a>(text: &'a str, verse: &Verse, separator: &str) -> &'a str {
+        text.strip_suffix(&format!("{}{}:{}", separator, verse.chapter, verse.verse))
+            .unwrap_or(text)
+    }
 }
 
+#[cfg(test)]
+mod unittests {
+    use super::*;
+
+    fn verse(chapter: u8, verse: u8, scripture: &str) -> Verse {
+        Verse {
+            reference: format!("John {}:{}", chapter, verse),
+            abbreviation: format!("Jn{}:{}", chapter, verse),
+            book: 43,
+            chapter,
+            verse,
+            scripture: scripture.to_string(),
+            words: Vec::new(),
+            spans: Vec::new(),
+            headings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn build_short_passage_uses_full_reference() {
+        let verses = vec![verse(3, 36, "He that believeth."), verse(4, 1, "When therefore.")];
+        let output = ResponseBuilder::build(&verses, 500, "KJV");
+        assert_eq!(
+            output.truncated,
+            "He that believeth. When therefore. - John 3:36-4:1 KJV"
+        );
+        assert_eq!(output.pages.len(), 1);
+        assert_eq!(output.last_verse, "John 4:1");
+    }
+
+    #[test]
+    fn build_pages_splits_passage() {
+        let verses: Vec<Verse> = (1..=6)
+            .map(|number| verse(1, number, "word ".repeat(10).trim_end()))
+            .collect();
+        let output = ResponseBuilder::build_pages(&verses, 120, "KJV", 2);
+        assert_eq!(output.pages.len(), 2);
+        assert!(output.pages.iter().all(|page| page.len() <= 120));
+        assert!(output.pages[0].ends_with(" - Jn1:1-2 KJV"));
+        assert!(output.pages[1].ends_with(" - Jn1:3-4 KJV"));
+        assert_eq!(output.last_verse, "John 1:4");
+        assert_eq!(output.remainder.split(' ').count(), 20);
+    }
+
+    #[test]
+    fn build_passages_packs_references() {
+        let passages = [
+            vec![verse(3, 16, "For God so loved.")],
+            vec![verse(3, 18, "He that believeth.")],
+            vec![verse(4, 1, &"word ".repeat(30))],
+        ];
+        let passages: Vec<&[Verse]> = passages.iter().map(Vec::as_slice).collect();
+        let output = ResponseBuilder::build_passages(&passages, 120, "KJV", 3);
+        assert_eq!(
+            output.pages[0],
+            "For God so loved. - John 3:16 KJV | He that believeth. - John 3:18 KJV"
+        );
+        assert_eq!(output.pages.len(), 3);
+        assert!(output.pages.iter().all(|page| page.len() <= 120));
+        assert_eq!(output.last_verse, "John 4:1");
+    }
+
+    #[test]
+    fn styled_renders_markup() {
+        let verses = [verse(3, 5, "Jesus answered, ‹Verily› I say{Or, truly}").with_markup()];
+        let styled = ResponseBuilder::styled(&verses, TextStyle::Brackets);
+        assert_eq!(styled[0].scripture, "Jesus answered, ‹Verily› I say");
+        assert!(styled[0].spans.is_empty());
+        assert_eq!(
+            ResponseBuilder::build(&styled, 500, "KJV").truncated,
+            "Jesus answered, ‹Verily› I say - John 3:5 KJV"
+        );
+        let plain = ResponseBuilder::styled(&verses, TextStyle::Plain);
+        assert_eq!(plain[0].scripture, "Jesus answered, Verily I say");
+    }
+
+    #[test]
+    fn styled_with_heading_starts_section() {
+        let mut verses = [verse(3, 1, "There was a man"), verse(3, 2, "The same came")];
+        verses[0].headings = vec!["Jesus and Nicodemus".to_string()];
+        let styled = ResponseBuilder::styled_with_heading(&verses, TextStyle::Plain);
+        assert_eq!(
+            ResponseBuilder::build(&styled, 500, "KJV").truncated,
+            "Jesus and Nicodemus — There was a man The same came - John 3:1-2 KJV"
+        );
+        let styled = ResponseBuilder::styled_with_heading(&verses[1..], TextStyle::Plain);
+        assert_eq!(styled[0].scripture, "The same came");
+    }
+
+    #[test]
+    fn build_comparison_shares_space() {
+        let short = [verse(1, 1, "In the beginning was the Word.")];
+        let long = [verse(1, 1, &"word ".repeat(40))];
+        let passages: [(&str, &[Verse]); 2] = [("KJV", &short), ("WEB", &long)];
+        let output = ResponseBuilder::build_comparison("John 1:1", &passages, 120);
+        assert!(output.starts_with("John 1:1 - KJV: In the beginning was the Word. | WEB: word"));
+        assert!(output.ends_with("word..."));
+        assert!(output.len() <= 120);
+    }
+
+    #[test]
+    fn build_pages_splits_long_verse() {
+        let verses = vec![verse(1, 1, "word ".repeat(40).trim_end())];
+        let output = ResponseBuilder::build_pages(&verses, 100, "KJV", 3);
+        assert_eq!(output.pages.len(), 3);
+        assert!(output.pages.iter().all(|page| page.ends_with(" - Jn1:1 KJV")));
+        assert!(output.pages.iter().all(|page| page.len() <= 100));
+    }
+}
@@ -1,14 +1,22 @@
 use crate::helpers::config::Config;
 use crate::helpers::env_variables::get_env_variable;
 use crate::helpers::Metrics;
-use bible::csv_import::bible_import;
+use bible::{binary_format, import};
 use bible::scripture::bible::Bible;
+use bible::scripture::cross_references::CrossReferences;
+use bible::scripture::markup::TextStyle;
+use bible::scripture::plan::ReadingPlan;
+use bible::scripture::strongs::Lexicon;
 use chrono::{DateTime, Local, Utc};
 use dashmap::DashMap;
 use lazy_static::lazy_static;
+use rayon::prelude::*;
 use regex::Regex;
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 use std::{env, fs};
 use tokio::sync::RwLock;
 
@@ -27,6 +35,12 @@ lazy_static! {
 
     pub static ref CHANNELS_PER_LISTENER: usize = 5;
     pub static ref  REPLY_CHARACTER_LIMIT: usize = 500;
+    // Longer passages such as whole chapters are split over this many replies, use !next for the rest.
+    pub static ref  REPLY_PAGE_LIMIT: usize = 3;
+    // !compare without translations shows the preferred one and the next few alphabetically.
+    pub static ref  COMPARE_TRANSLATION_LIMIT: usize = 3;
+    // !xref lists at most this many related passages.
+    pub static ref  CROSS_REFERENCE_LIMIT: usize = 8;
     // The only reason we use KJV as default is that it's free to use from copyright restrictions.
     pub static ref  DEFAULT_TRANSLATION: String = "KJV".to_string();
 
@@ -36,6 +50,8 @@ lazy_static! {
     pub static ref START_DATETIME_UTC_STRING: String = START_DATETIME_UTC.format("%Y/%m/%d %H:%M UTC").to_string();
 
     static ref COMMAND_PREFIXES: DashMap<String, char> = DashMap::new();
+    static ref TEXT_STYLES: DashMap<String, TextStyle> = DashMap::new();
+    static ref SUGGESTIONS: DashMap<String, bool> = DashMap::new();
 
     pub static ref START_DATETIME_LOCAL: DateTime<Local> = Local::now();
     pub static ref START_DATETIME_LOCAL_STRING: String = {
@@ -70,8 +86,6 @@ pub static ref BIBLES: Arc<HashMap<String, Arc<Bible>>> = {
                 }
             };
 
-            let mut bibles = HashMap::new();
-
             let files = match fs::read_dir(bibles_directory) {
                 Ok(files) => files,
                 Err(e) => {
@@ -80,6 +94,9 @@ pub static ref BIBLES: Arc<HashMap<String, Arc<Bible>>> = {
                 }
             };
 
+            // A translation may be present in several formats, e.g. KJV.csv and the KJV.cvb converted from it,
+            // so group the files by translation and try the preferred format first.
+            let mut translations: HashMap<String, Vec<(usize, PathBuf)>> = HashMap::new();
             for file in files {
                 let entry = match file {
                     Ok(entry) => entry,
@@ -89,32 +106,129 @@ pub static ref BIBLES: Arc<HashMap<String, Arc<Bible>>> = {
                     }
                 };
 
-                if entry.path().is_file()
-                    && entry.path().extension().and_then(|s| s.to_str()) == Some("csv")
-                {
-                    let file_stem = entry
-                        .path()
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or_default()
-                        .to_string()
-                        .to_uppercase();
-                    let file_path = entry.path().to_string_lossy().to_string();
-                    match bible_import(&entry.path().to_string_lossy()) {
-                        Ok(imported_bible) => {
-                            bibles.insert(file_stem, Arc::new(imported_bible));
-                        }
-                        Err(err) => {
-                            eprintln!("Error running import for file '{}': {}", file_path, err);
-                        }
-                    }
-                }
+                let path = entry.path();
+                let Some(preference) = import::preference(&path) else {
+                    continue;
+                };
+                let file_stem = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default()
+                    .to_string()
+                    .to_uppercase();
+                translations.entry(file_stem).or_default().push((preference, path));
             }
 
+            // Translations load in parallel, falling back to the next format if the preferred one fails.
+            let bibles = translations
+                .into_par_iter()
+                .filter_map(|(translation, mut paths)| {
+                    paths.sort();
+                    skip_stale_precompiled(&mut paths);
+                    paths.into_iter().find_map(|(_, path)| {
+                        let file_path = path.to_string_lossy().to_string();
+                        match import::bible_import(&file_path) {
+                            Ok(imported_bible) => Some((translation.clone(), Arc::new(imported_bible))),
+                            Err(err) => {
+                                eprintln!("Error running import for file '{}': {}", file_path, err);
+                                None
+                            }
+                        }
+                    })
+                })
+                .collect();
+
             Arc::new(bibles)
         };
+
+    // Cross references such as the OpenBible.info cross_references.txt, loaded the first time !xref is used.
+    pub static ref CROSS_REFERENCES: CrossReferences = {
+        let cross_references_path = get_env_variable("CROSS_REFERENCES_PATH", "cross_references.txt");
+        CrossReferences::import(&cross_references_path).unwrap_or_else(|e| {
+            eprintln!("Error importing cross references '{}': {}", cross_references_path, e);
+            CrossReferences::new()
+        })
+    };
+
+    // Strong's dictionary, the entries that come with ChapterVerse with any from a fuller dictionary file over them.
+    pub static ref LEXICON: Lexicon = {
+        let mut lexicon = Lexicon::embedded();
+        let lexicon_path = get_env_variable("LEXICON_PATH", "strongs_lexicon.tsv");
+        if Path::new(&lexicon_path).exists() {
+            match Lexicon::import(&lexicon_path) {
+                Ok(dictionary) => lexicon.extend(dictionary),
+                Err(e) => eprintln!("Error importing lexicon '{}': {}", lexicon_path, e),
+            }
+        }
+        lexicon
+    };
+
+    // The bundled reading plans followed by any plan files, e.g. plans/psalms.toml with a title
+    // and days = [["Psalm 1", "Psalm 2"], ["Psalm 3"]], started by its file name.
+    pub static ref READING_PLANS: Vec<ReadingPlan> = {
+        let mut plans = ReadingPlan::bundled();
+        let plans_path = get_env_variable("READING_PLANS_PATH", "plans");
+        let Ok(files) = fs::read_dir(plans_path) else {
+            return plans;
+        };
+        for path in files.filter_map(|file| file.ok()).map(|entry| entry.path()) {
+            if path.extension().and_then(|s| s.to_str()) != Some("toml") {
+                continue;
+            }
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_lowercase();
+            let plan = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| toml::from_str::<PlanFile>(&contents).map_err(|e| e.to_string()))
+                .and_then(|file| {
+                    ReadingPlan::from_days(&name, file.title.as_deref().unwrap_or(&name), &file.days)
+                        .map_err(|e| e.to_string())
+                });
+            match plan {
+                Ok(plan) => {
+                    plans.retain(|bundled| bundled.name != plan.name);
+                    plans.push(plan);
+                }
+                Err(e) => eprintln!("Error loading reading plan '{}': {}", path.display(), e),
+            }
+        }
+        plans
+    };
     }
 
+#[derive(Deserialize)]
+struct PlanFile {
+    title: Option<String>,
+    days: Vec<Vec<String>>,
+}
+
+/// Finds a reading plan by name, ignoring case, spaces and punctuation, e.g. "NT 90" or "M'Cheyne".
+pub fn find_reading_plan(name: &str) -> Option<&'static ReadingPlan> {
+    let simplify = |text: &str| {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let name = simplify(name);
+    READING_PLANS
+        .iter()
+        .find(|plan| simplify(&plan.name) == name || simplify(&plan.title) == name)
+}
+
+/// A cheap check before looking a message over for references: a number straight after a word,
+/// as in "john 3:16", "ps 23" or "1. mose 1,1".  Chat such as "12:45" or "3-2" is not looked at.
+pub fn possible_scripture(text: &str) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    chars.windows(2).any(|pair| pair[0].is_alphabetic() && pair[1].is_ascii_digit())
+        || chars.windows(3).any(|three| {
+            three[0].is_alphabetic() && matches!(three[1], ' ' | '.') && three[2].is_ascii_digit()
+        })
+}
+
 pub fn find_bible(input: String, default: &String) -> String {
     BIBLES_REGEX
         .find(&input)
@@ -122,6 +236,24 @@ pub fn find_bible(input: String, default: &String) -> String {
         .unwrap_or_else(|| default.to_string())
 }
 
+/// Splits command parameters such as "1 john 1:9 kjv web" into the reference words and the
+/// loaded translations named after them, in the order given.
+pub fn split_translations(params: &[String]) -> (&[String], Vec<String>) {
+    let mut translations = Vec::new();
+    let mut reference_words = params;
+    while let Some((last, rest)) = reference_words.split_last() {
+        let translation = last.to_uppercase();
+        if !BIBLES.contains_key(&translation) {
+            break;
+        }
+        if !translations.contains(&translation) {
+            translations.insert(0, translation);
+        }
+        reference_words = rest;
+    }
+    (reference_words, translations)
+}
+
 pub fn avaialble_bibles() -> String {
     BIBLES
         .keys()
@@ -130,6 +262,39 @@ pub fn avaialble_bibles() -> String {
         .join(", ")
 }
 
+// A precompiled Bible older than a source of the same translation, e.g. a KJV.cvb after KJV.csv was
+// edited, is out of date, so the source is loaded first until the translation is converted again.
+fn skip_stale_precompiled(paths: &mut [(usize, PathBuf)]) {
+    let Some(((_, precompiled), sources)) = paths.split_first() else {
+        return;
+    };
+    if precompiled.extension().and_then(|s| s.to_str()) != Some(binary_format::EXTENSION) {
+        return;
+    }
+    let converted = modified(precompiled);
+    if let Some((_, source)) = sources.iter().find(|(_, source)| modified(source) > converted) {
+        eprintln!(
+            "Warning: '{}' is older than '{}', loading the source instead. Convert it again to load faster.",
+            precompiled.display(),
+            source.display()
+        );
+        paths.rotate_left(1);
+    }
+}
+
+// When a file was last changed, or the latest of its files for a USFM directory.
+fn modified(path: &Path) -> Option<SystemTime> {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| entry.metadata().and_then(|metadata| metadata.modified()).ok())
+            .max()
+    } else {
+        fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+    }
+}
+
 pub fn get_running_time() -> String {
     let duration = Utc::now().signed_duration_since(*START_DATETIME_UTC);
     let days = duration.num_days();
@@ -154,3 +319,31 @@ pub fn update_command_prefix(channel: &String, prefix: &char) {
     let channel_lower = channel.to_lowercase();
     COMMAND_PREFIXES.insert(channel_lower.clone(), *prefix);
 }
+
+pub fn lookup_text_style(channel: &str) -> TextStyle {
+    let channel_lower = channel.to_lowercase();
+    if let Some(style) = TEXT_STYLES.get(&channel_lower) {
+        return *style;
+    }
+    let fetched_style = Config::load(&channel_lower).get_text_style();
+    TEXT_STYLES.insert(channel_lower, fetched_style);
+    fetched_style
+}
+
+pub fn update_text_style(channel: &str, style: TextStyle) {
+    TEXT_STYLES.insert(channel.to_lowercase(), style);
+}
+
+pub fn lookup_suggestions(channel: &str) -> bool {
+    let channel_lower = channel.to_lowercase();
+    if let Some(suggestions) = SUGGESTIONS.get(&channel_lower) {
+        return *suggestions;
+    }
+    let fetched_suggestions = Config::load(&channel_lower).get_suggestions();
+    SUGGESTIONS.insert(channel_lower, fetched_suggestions);
+    fetched_suggestions
+}
+
+pub fn update_suggestions(channel: &str, suggestions: bool) {
+    SUGGESTIONS.insert(channel.to_lowercase(), suggestions);
+}
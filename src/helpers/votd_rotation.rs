@@ -0,0 +1,100 @@
+use chrono::{Datelike, NaiveDate};
+use std::error::Error;
+use std::fs;
+
+// The built-in list, used unless VOTD_LIST in votd_config.toml names another file.
+const DEFAULT_REFERENCES: &str = include_str!("../../votd_references.txt");
+
+/// The verse of the day from a list of references, the built-in one when `list_path` is empty,
+/// so the verse of the day works without an external source.
+pub fn verse_of_the_day(list_path: &str, date: NaiveDate) -> Result<String, Box<dyn Error>> {
+    let list = if list_path.is_empty() {
+        DEFAULT_REFERENCES.to_string()
+    } else {
+        fs::read_to_string(list_path)?
+    };
+    reference_for(&references(&list), date)
+        .map(str::to_string)
+        .ok_or_else(|| format!("No references in the verse of the day list {}", list_path).into())
+}
+
+// One reference per line, leaving out blank lines and # comments.
+fn references(list: &str) -> Vec<&str> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+// Each year walks the list from a different start with a different step.  The step shares no
+// factor with the length of the list, so every day of a year gets a different reference as long
+// as the list has one for every day.
+fn reference_for<'a>(references: &[&'a str], date: NaiveDate) -> Option<&'a str> {
+    let length = references.len() as u64;
+    if length == 0 {
+        return None;
+    }
+    let year = date.year().unsigned_abs() as u64;
+    let start = year * 7919 % length;
+    let mut step = (year * 104_729 % length).max(1);
+    while gcd(step, length) != 1 {
+        step += 1;
+    }
+    let index = (start + u64::from(date.ordinal0()) * step) % length;
+    references.get(index as usize).copied()
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod unittests {
+    use super::*;
+    use bible::scripture::books::Book;
+    use bible::scripture::reference::Reference;
+    use std::collections::HashSet;
+
+    #[test]
+    fn no_repeats_within_a_year() {
+        let references = references(DEFAULT_REFERENCES);
+        for year in [2024, 2025] {
+            let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+            let days: HashSet<&str> = first
+                .iter_days()
+                .take_while(|date| date.year() == year)
+                .filter_map(|date| reference_for(&references, date))
+                .collect();
+            let leap_year = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+            assert_eq!(days.len(), if leap_year { 366 } else { 365 });
+        }
+
+        let date = NaiveDate::from_ymd_opt(2025, 10, 18).unwrap();
+        assert_eq!(
+            verse_of_the_day("", date).unwrap(),
+            verse_of_the_day("", date).unwrap()
+        );
+        assert_eq!(reference_for(&["John 3:16"], date), Some("John 3:16"));
+        assert_eq!(reference_for(&[], date), None);
+    }
+
+    #[test]
+    fn built_in_references_are_verses() {
+        for text in references(DEFAULT_REFERENCES) {
+            let reference: Reference = text.parse().unwrap();
+            for location in [reference.start, reference.end] {
+                let book = Book::get(location.book).unwrap();
+                assert_eq!(
+                    book.check_verse(location.chapter, location.verse.unwrap_or(1)),
+                    Ok(()),
+                    "{}",
+                    text
+                );
+            }
+        }
+    }
+}
@@ -2,45 +2,86 @@ use chrono::{NaiveTime, Utc};
 use regex::Regex;
 use reqwest::{self, StatusCode};
 use std::error::Error;
+use std::time::Duration;
 
+use super::votd_rotation;
 use super::ConfigManager;
 
+/// The reference of today's verse of the day, from the providers listed in VOTD_PROVIDERS in
+/// the order given: "remote" fetches it from VOTD_URL and "offline" picks it from the
+/// reference list.  Each day's verse is kept in votd_config.toml so it does not change.
 pub async fn fetch_verse_of_the_day() -> Result<String, Box<dyn Error>> {
     let mut config_manager = ConfigManager::new("./votd_config.toml");
     let switch_time_str = config_manager.get_string("NEW_DAY_TIME", "01:00");
     let switch_time = NaiveTime::parse_from_str(&switch_time_str, "%H:%M").unwrap_or_default();
     let current_datetime = Utc::now();
 
-    let date_to_check = if current_datetime.time() > switch_time {
-        current_datetime.format("%Y%m%d").to_string()
+    let date = if current_datetime.time() > switch_time {
+        current_datetime.date_naive()
     } else {
-        (current_datetime - chrono::Duration::days(1))
-            .format("%Y%m%d")
-            .to_string()
+        (current_datetime - chrono::Duration::days(1)).date_naive()
     };
+    let date_to_check = date.format("%Y%m%d").to_string();
 
     // println!("{}:", date_to_check);
     let mut votd = config_manager.get_string(&date_to_check, "");
 
     if votd.is_empty() {
-        let url = config_manager.get_string("VOTD_URL", "https://bible-api.com/?random=verse");
-        let regex_pattern = config_manager.get_string("VOTD_REGEX", r#""reference":"([^"]+)"#);
-        let response = reqwest::Client::new().get(&url).send().await?;
-
-        if response.status() != StatusCode::OK {
-            return Err(format!("Failed to fetch data: {}", response.status()).into());
+        let providers = config_manager.get_string("VOTD_PROVIDERS", "remote, offline");
+        let mut errors = Vec::new();
+        for provider in providers
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            let result = match provider.to_lowercase().as_str() {
+                "remote" => fetch_remote_verse(&mut config_manager).await,
+                "offline" => {
+                    let list_path = config_manager.get_string("VOTD_LIST", "");
+                    votd_rotation::verse_of_the_day(&list_path, date)
+                }
+                _ => Err("Unknown provider, use remote or offline".into()),
+            };
+            match result {
+                Ok(reference) => {
+                    votd = reference;
+                    break;
+                }
+                Err(e) => errors.push(format!("{}: {}", provider, e)),
+            }
         }
 
-        let body = response.text().await?;
-        let re = Regex::new(&regex_pattern)?;
-
-        if let Some(caps) = re.captures(&body) {
-            votd = caps.get(1).unwrap().as_str().to_string();
-            config_manager.set_string(&date_to_check, &votd);
-        } else {
-            return Err("Verse reference not found".into());
+        if votd.is_empty() {
+            return Err(format!(
+                "No verse of the day provider succeeded. {}",
+                errors.join(", ")
+            )
+            .into());
         }
+        config_manager.set_string(&date_to_check, &votd);
     }
 
     Ok(votd)
 }
+
+async fn fetch_remote_verse(config_manager: &mut ConfigManager) -> Result<String, Box<dyn Error>> {
+    let url = config_manager.get_string("VOTD_URL", "https://bible-api.com/?random=verse");
+    let regex_pattern = config_manager.get_string("VOTD_REGEX", r#""reference":"([^"]+)"#);
+    // A slow site should not hold up the verse of the day, the offline provider can answer instead.
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+    let response = client.get(&url).send().await?;
+
+    if response.status() != StatusCode::OK {
+        return Err(format!("Failed to fetch data: {}", response.status()).into());
+    }
+
+    let body = response.text().await?;
+    let re = Regex::new(&regex_pattern)?;
+
+    match re.captures(&body) {
+        Some(caps) => Ok(caps.get(1).unwrap().as_str().to_string()),
+        None => Err("Verse reference not found".into()),
+    }
+}
@@ -4,7 +4,9 @@ use log::info;
 use env_logger;
 use futures::future::pending;
 use tokio::sync::mpsc;
-use bible::scripture::bible::Bible;
+use bible::scripture::bible::{Bible, Verse};
+use bible::scripture::books::Book;
+use bible::scripture::reference::ReferenceError;
 use commands::*;
 use helpers::config::Config;
 use helpers::Metrics;
@@ -22,6 +24,11 @@ mod helpers;
 #[tokio::main]
 async fn main() {
     env_logger::init();
+    // Subcommands such as "convert" run and exit without starting the bot.
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(exit_code) = helpers::cli::run(&args) {
+        std::process::exit(exit_code);
+    }
     initialize_statics();
     PrintCommand::System.print_message("ChapterVerse", "Jesus is Lord!");
     PrintCommand::Issue.print_message("Version", env!("CARGO_PKG_VERSION"));
@@ -122,13 +129,15 @@ async fn handle_twitch_messages(mut listener_reciever: mpsc::UnboundedReceiver<M
                         message.tags.push(Type::PossibleCommand);
                     } else if message_text_lowercase.contains("gospel message") {
                         message.tags.push(Type::Gospel);
-                    } else if message_text_lowercase.contains(":") {
+                    } else if possible_scripture(&message_text_lowercase) {
                         message.tags.push(Type::PossibleScripture);
                     } else {
                         message.tags.push(Type::None);
                     }
 
                     let mut reply: Option<String> = None;
+                    // Further replies sent after the first one, e.g. the rest of a whole chapter.
+                    let mut reply_pages: Vec<String> = Vec::new();
                     let display_name = message.display_name.unwrap();
                     let message_text = message.text.to_string();
 
@@ -226,6 +235,53 @@ async fn handle_twitch_messages(mut listener_reciever: mpsc::UnboundedReceiver<M
                                         Metrics::add_user(&METRICS, &display_name).await;
                                         random(channel, display_name, params).await
                                     }
+                                    "!plan" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        plan(channel, display_name, params).await
+                                    }
+                                    "!xref" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        xref(display_name, params).await
+                                    }
+                                    "!strongs" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        strongs(params).await
+                                    }
+                                    "!word" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        word(display_name, params).await
+                                    }
+                                    "!outline" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        outline(display_name, params).await
+                                    }
+                                    "!search" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        // Searches keep the original case and quotes, e.g. "living water" OR well.
+                                        let params: Vec<String> = message_text.split_whitespace().skip(1).map(|s| s.to_string()).collect();
+                                        search(display_name, params).await
+                                    }
+                                    "!book" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        book(params).await
+                                    }
+                                    "!compare" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        compare(channel, display_name, params).await
+                                    }
+                                    "!diff" => {
+                                        message.tags.push(Type::Command);
+                                        Metrics::add_user(&METRICS, display_name).await;
+                                        diff(display_name, params).await
+                                    }
                                     "!next" => {
                                         message.tags.push(Type::Command);
                                         Metrics::add_user(&METRICS, &display_name).await;
@@ -339,6 +395,32 @@ async fn handle_twitch_messages(mut listener_reciever: mpsc::UnboundedReceiver<M
                                             (None, _) => None,
                                         }
                                     }
+                                    "!textstyle" => {
+                                        message.tags.push(Type::Command);
+                                        message.tags.push(Type::ExcludeMetrics);
+                                        Metrics::add_user(&METRICS, display_name).await;
+
+                                        match textstyle(display_name, params).await {
+                                            (Some(message), style) => {
+                                                update_text_style(display_name, style);
+                                                Some(message)
+                                            }
+                                            (None, _) => None,
+                                        }
+                                    }
+                                    "!suggestions" => {
+                                        message.tags.push(Type::Command);
+                                        message.tags.push(Type::ExcludeMetrics);
+                                        Metrics::add_user(&METRICS, display_name).await;
+
+                                        match suggestions(display_name, params).await {
+                                            (Some(message), suggestions) => {
+                                                update_suggestions(display_name, suggestions);
+                                                Some(message)
+                                            }
+                                            (None, _) => None,
+                                        }
+                                    }
                                     "!gospel" => {
                                         message.tags.push(Type::Gospel);
                                         Metrics::add_user(&METRICS, &display_name).await;
@@ -377,20 +459,36 @@ async fn handle_twitch_messages(mut listener_reciever: mpsc::UnboundedReceiver<M
                                 if let Some(bible_arc) = BIBLES.get(&bible_name_to_use) {
                                     let bible: &Bible = &*bible_arc;
                                     reply = {
-                                        let verses = bible.get_scripture(&message.text);
-                                        if verses.is_empty() {
+                                        // Translation names are left out so "psalm 23 nkjv" is still a whole chapter on its own, while "John 5 times" is not a chapter at all.
+                                        let reference = BIBLES_REGEX.replace_all(&message_text, "");
+                                        let passages = bible.get_scriptures(&reference);
+                                        if passages.is_empty() {
                                             message.tags.push(Type::NotScripture);
-                                            None
+                                            // Only explain what is wrong when the whole message is one reference, e.g. "John 22:1", so chat such as "it is 12:45" stays quiet.
+                                            // An unknown book is only answered when it looks like a misspelt one, so "lunch 12:30" gets no reply, and only in channels with suggestions on.
+                                            match bible.lookup(&reference) {
+                                                Err(ReferenceError::NotAReference(_)) => None,
+                                                Err(ReferenceError::UnknownBook(_)) if !reference.contains(':') || !lookup_suggestions(channel) => None,
+                                                Err(ReferenceError::UnknownBook(book)) => Book::suggest(&book).map(|suggestion| {
+                                                    format!("{} is not a book of the Bible, did you mean {}?", book, reference.replacen(&book, suggestion.name, 1).trim())
+                                                }),
+                                                Err(e) => Some(e.to_string()),
+                                                Ok(_) => None,
+                                            }
                                         } else {
                                             //@TwitchAccountName + 1 extra space because the name is included in the text that can't exceed 500.
                                             let adjusted_character_limit = *REPLY_CHARACTER_LIMIT
                                                 - (message.display_name.unwrap().len() + 1);
-                                            let response_output = ResponseBuilder::build(
-                                                &verses,
+                                            let style = lookup_text_style(channel);
+                                            let styled: Vec<Vec<Verse>> = passages.iter().map(|passage| ResponseBuilder::styled_with_heading(passage, style)).collect();
+                                            let passages: Vec<&[Verse]> = styled.iter().map(Vec::as_slice).collect();
+                                            let mut response_output = ResponseBuilder::build_passages(
+                                                &passages,
                                                 adjusted_character_limit,
                                                 &bible_name_to_use,
+                                                *REPLY_PAGE_LIMIT,
                                             );
-                                            config.set_last_verse(&response_output.last_verse);
+                                            config.set_last_verse(&bible.to_kjv_reference(&response_output.last_verse));
                                             config.add_account_metrics_scriptures();
 
                                             if !channel.eq_ignore_ascii_case(display_name) {
@@ -402,6 +500,7 @@ async fn handle_twitch_messages(mut listener_reciever: mpsc::UnboundedReceiver<M
                                             Metrics::add_user(&METRICS, &display_name).await;
                                             Metrics::increment_total_scriptures(&METRICS).await;
                                             message.tags.push(Type::Scripture);
+                                            reply_pages = response_output.pages.split_off(1);
                                             Some(response_output.truncated)
                                         }
                                     };
@@ -438,6 +537,16 @@ async fn handle_twitch_messages(mut listener_reciever: mpsc::UnboundedReceiver<M
                                     eprintln!("Failed to send message: {}", e);
                                 }
 
+                                for page in reply_pages.drain(..) {
+                                    let mut page_message = message.clone();
+                                    page_message.reply = Some(page);
+                                    if let Err(e) =
+                                        { replier_transmitter_clone.message_tx.send(page_message) }
+                                    {
+                                        eprintln!("Failed to send message: {}", e);
+                                    }
+                                }
+
                                 if message.tags.contains(&Type::WHISPER)
                                     || !message
                                     .channel
@@ -497,11 +606,21 @@ mod unittests {
             let bible: &Bible = &*bible_arc; // Here you dereference the Arc and immediately borrow the result
 
             let message = match bible.get_scripture("2 Timothy 3:16") {
-                Some(verse) => format!("{}", verse.scripture),
-                None => "Verse not found".to_string(),
+                verses if !verses.is_empty() => verses[0].scripture.clone(),
+                _ => "Verse not found".to_string(),
             };
 
             PrintCommand::Info.print_message(&format!("{}, 2 Timothy 3:16", bible_name), &message);
         }
     }
+
+    #[test]
+    fn possible_scripture_messages() {
+        for text in ["john 3:16", "ps 23 kjv", "1. mose 1,1", "read jn3:16"] {
+            assert!(possible_scripture(text), "{}", text);
+        }
+        for text in ["12:45", "3-2 what a game", "9/10 would watch again"] {
+            assert!(!possible_scripture(text), "{}", text);
+        }
+    }
 }